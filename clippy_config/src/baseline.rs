@@ -0,0 +1,213 @@
+//! Baseline files for `cargo clippy --baseline`, recording the Clippy diagnostics that shouldn't be
+//! reported anymore.
+//!
+//! The diagnostics are identified by their lint, their file and a hash of the code they point to,
+//! with its whitespace normalized, so that the baseline keeps matching when the code around them
+//! changes.
+//!
+//! `clippy-driver` drops the diagnostics of the baseline before they're emitted, so that they don't
+//! fail the compilation. For `--write-baseline`, it appends them to a file of [`Record`]s instead,
+//! which `cargo clippy` turns into the baseline once Cargo is done.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write as _};
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The version of the format of the baseline files.
+const VERSION: u32 = 1;
+
+#[derive(Deserialize, Serialize)]
+struct BaselineFile {
+    version: u32,
+    entries: Vec<Entry>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct Entry {
+    /// The lint name e.g. `clippy::bytes_nth`
+    lint: String,
+    /// The file containing the diagnostic, relative to the workspace root.
+    file: String,
+    snippet_hash: String,
+    /// The message of the diagnostic, to make the file easier to review. It isn't used for the
+    /// matching.
+    message: String,
+    /// How many diagnostics of the file match the entry.
+    count: usize,
+}
+
+/// What identifies the diagnostics of an entry of the baseline.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct Key {
+    lint: String,
+    file: String,
+    snippet_hash: String,
+}
+
+impl Key {
+    /// Returns the key of a diagnostic of `lint`, e.g. `clippy::bytes_nth`, in `file`, as printed
+    /// in the diagnostics, pointing to `snippet`.
+    pub fn new(lint: &str, file: &str, snippet: &str) -> Self {
+        let snippet = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+        Self {
+            lint: lint.to_owned(),
+            file: file.replace('\\', "/"),
+            snippet_hash: format!("{:016x}", fnv1a(snippet.as_bytes())),
+        }
+    }
+}
+
+/// The 64-bit FNV-1a hash, which unlike the hashers of the standard library is guaranteed to stay
+/// the same across Rust versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// The diagnostics of a baseline, as read by `clippy-driver`.
+pub struct Baseline {
+    /// How many more diagnostics each entry can drop.
+    entries: Mutex<HashMap<Key, usize>>,
+    suppressed: AtomicUsize,
+}
+
+impl Baseline {
+    /// Reads the baseline at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read, or isn't a baseline of this version of Clippy.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("couldn't read the baseline `{}`: {e}", path.display()))?;
+        let file: BaselineFile = serde_json::from_str(&contents)
+            .map_err(|e| format!("couldn't parse the baseline `{}`: {e}", path.display()))?;
+        if file.version != VERSION {
+            return Err(format!(
+                "the baseline `{}` has the unsupported version {}, regenerate it with `--write-baseline`",
+                path.display(),
+                file.version
+            ));
+        }
+        let mut entries = HashMap::new();
+        for entry in file.entries {
+            let key = Key {
+                lint: entry.lint,
+                file: entry.file,
+                snippet_hash: entry.snippet_hash,
+            };
+            *entries.entry(key).or_default() += entry.count;
+        }
+        Ok(Self {
+            entries: Mutex::new(entries),
+            suppressed: AtomicUsize::new(0),
+        })
+    }
+
+    /// Returns whether the diagnostic is in the baseline and shouldn't be reported, using up one of
+    /// the diagnostics its entry matches.
+    pub fn suppresses(&self, key: &Key) -> bool {
+        let mut entries = self.entries.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(count) = entries.get_mut(key)
+            && *count > 0
+        {
+            *count -= 1;
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// How many diagnostics were suppressed so far.
+    pub fn suppressed(&self) -> usize {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+}
+
+/// A Clippy diagnostic recorded by `clippy-driver` for `--write-baseline`.
+#[derive(Deserialize, Serialize)]
+pub struct Record {
+    /// Identifies the compilation which emitted the diagnostic. Cargo compiles a file for every
+    /// target containing it, e.g. for the library and its unit tests, whose diagnostics must only
+    /// be recorded once.
+    pub unit: String,
+    pub key: Key,
+    pub message: String,
+}
+
+impl Record {
+    /// Appends the record to the file of records at `path`. A single write keeps the records of the
+    /// crates compiled in parallel apart.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written.
+    pub fn append(&self, path: &Path) -> io::Result<()> {
+        let line = serde_json::to_string(self).expect("the record can be serialized") + "\n";
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(line.as_bytes())
+    }
+}
+
+/// Writes the baseline of the diagnostics in the file of records at `records` to `path`, and
+/// returns how many diagnostics it contains. No file of records means there was no diagnostic.
+///
+/// # Errors
+///
+/// Returns an error if the records can't be read or the baseline can't be written.
+pub fn write(path: &Path, records: &Path) -> Result<usize, String> {
+    let contents = match fs::read_to_string(records) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("couldn't read the diagnostics to record in the baseline: {e}")),
+    };
+    let mut by_unit: HashMap<(String, Key), usize> = HashMap::new();
+    let mut messages = HashMap::new();
+    for line in contents.lines() {
+        let record: Record = serde_json::from_str(line)
+            .map_err(|e| format!("couldn't parse the diagnostics to record in the baseline: {e}"))?;
+        *by_unit.entry((record.unit, record.key.clone())).or_default() += 1;
+        messages.entry(record.key).or_insert(record.message);
+    }
+    // The compilations of a file find the same diagnostics, or more with the `cfg(test)` code
+    let mut counts: BTreeMap<Key, usize> = BTreeMap::new();
+    for ((_, key), count) in by_unit {
+        let max = counts.entry(key).or_default();
+        *max = (*max).max(count);
+    }
+
+    let total = counts.values().sum();
+    let file = BaselineFile {
+        version: VERSION,
+        entries: counts
+            .into_iter()
+            .map(|(key, count)| {
+                let message = messages.remove(&key).unwrap_or_default();
+                Entry {
+                    lint: key.lint,
+                    file: key.file,
+                    snippet_hash: key.snippet_hash,
+                    message,
+                    count,
+                }
+            })
+            .collect(),
+    };
+    let contents = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    fs::write(path, contents + "\n").map_err(|e| format!("couldn't write the baseline `{}`: {e}", path.display()))?;
+    Ok(total)
+}
+
+/// Returns e.g. `1 diagnostic` or `2 diagnostics`, for the notes about the baseline.
+pub fn plural_diagnostics(count: usize) -> String {
+    format!("{count} diagnostic{}", if count == 1 { "" } else { "s" })
+}
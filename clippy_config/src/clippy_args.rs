@@ -0,0 +1,296 @@
+//! The arguments `cargo clippy` passes to `clippy-driver` in the `CLIPPY_ARGS` environment
+//! variable, as a JSON object.
+//!
+//! The driver tells Cargo to lint a crate again when the value changes, so the options that
+//! `cargo-clippy` handles by itself from Cargo's JSON messages, like `--summary` or `--sarif`,
+//! aren't part of it: changing them doesn't need to lint anything again.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The version of the format, increased on every change that a `clippy-driver` of another version
+/// wouldn't read the same way.
+pub const CLIPPY_ARGS_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClippyArgs {
+    /// The version of the format, [`CLIPPY_ARGS_VERSION`] when written by this version of Clippy.
+    /// It's only missing, and 0, in invalid values.
+    pub version: u32,
+    /// The version of the `cargo-clippy` which wrote the arguments, for the error message when the
+    /// driver can't read them.
+    pub cargo_clippy_version: String,
+    /// Only lint the primary packages, set by `--no-deps`.
+    pub no_deps: bool,
+    /// The directory to look for the configuration in instead of the package, set by `--conf-dir`.
+    pub conf_dir: Option<PathBuf>,
+    /// The lint profile of the configuration to apply, set by `--lint-profile`.
+    pub lint_profile: Option<String>,
+    /// The lint level flags given after `--`, in order.
+    pub lint_levels: Vec<LintLevel>,
+    /// The other arguments given after `--`, passed to the compiler as is.
+    pub rustc_args: Vec<String>,
+    /// The lints whose suggestions `--fix` applies, set by `--fix-only`. The names are normalized,
+    /// without the `clippy::` prefix.
+    pub fix_only: Option<Vec<String>>,
+    /// Whether `--fix` also applies the suggestions which may be incorrect, set by
+    /// `--fix-applicability maybe-incorrect`.
+    pub fix_maybe_incorrect: bool,
+    /// Set by `--print-config` to a new value on every run, so that no crate is skipped.
+    pub print_config: Option<String>,
+    /// Where to write the lint pass times, like the `CLIPPY_PROFILE` environment variable.
+    pub profile: Option<String>,
+    /// The only lines to report the lints of, set by `--changed-since` or `--changed-lines`.
+    pub changed_lines: Option<ChangedLines>,
+    /// The absolute path of the baseline whose diagnostics aren't reported, set by `--baseline`.
+    pub baseline: Option<PathBuf>,
+    /// The file to record the Clippy diagnostics in for `--write-baseline`, a new one on every run
+    /// so that no crate is skipped.
+    pub baseline_records: Option<PathBuf>,
+}
+
+/// The lines changed since a Git revision or by a diff. The driver drops the lints pointing
+/// elsewhere before they're emitted, so that they don't fail the compilation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ChangedLines {
+    /// The changes of each file, by absolute path.
+    pub files: BTreeMap<PathBuf, FileChanges>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileChanges {
+    /// A new file, like the files Git doesn't track yet.
+    All,
+    /// The ranges of changed lines, inclusive.
+    Lines(Vec<(usize, usize)>),
+}
+
+impl ChangedLines {
+    /// Whether the lines `start..=end` of the file at `path` overlap the changed lines.
+    pub fn contains(&self, path: &Path, start: usize, end: usize) -> bool {
+        match self.files.get(path) {
+            Some(FileChanges::All) => true,
+            Some(FileChanges::Lines(lines)) => lines
+                .iter()
+                .any(|&(changed_start, changed_end)| changed_start <= end && start <= changed_end),
+            None => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Level {
+    Allow,
+    Warn,
+    ForceWarn,
+    Deny,
+    Forbid,
+}
+
+impl Level {
+    fn from_flag(flag: &str) -> Option<Self> {
+        match flag {
+            "-A" | "--allow" => Some(Self::Allow),
+            "-W" | "--warn" => Some(Self::Warn),
+            "--force-warn" => Some(Self::ForceWarn),
+            "-D" | "--deny" => Some(Self::Deny),
+            "-F" | "--forbid" => Some(Self::Forbid),
+            _ => None,
+        }
+    }
+
+    /// Returns the level set by a lint level flag like `-W`, `-Wclippy::pedantic` or
+    /// `--warn=clippy::pedantic`, along with the lint if it's part of the same argument.
+    fn split_flag(arg: &str) -> Option<(Self, Option<&str>)> {
+        if let Some(level) = Self::from_flag(arg) {
+            Some((level, None))
+        } else if let Some((flag, lint)) = arg.split_once('=')
+            && flag.starts_with("--")
+        {
+            Some((Self::from_flag(flag)?, Some(lint)))
+        } else {
+            let level = Self::from_flag(arg.get(..2)?)?;
+            Some((level, Some(&arg[2..])))
+        }
+    }
+
+    fn flag(self) -> &'static str {
+        match self {
+            Self::Allow => "--allow",
+            Self::Warn => "--warn",
+            Self::ForceWarn => "--force-warn",
+            Self::Deny => "--deny",
+            Self::Forbid => "--forbid",
+        }
+    }
+}
+
+/// A lint level set on the command line, like `-W clippy::pedantic`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LintLevel {
+    pub level: Level,
+    /// The lint or lint group, e.g. `clippy::pedantic` or `warnings`.
+    pub lint: String,
+}
+
+impl ClippyArgs {
+    /// Returns empty arguments for this version of Clippy.
+    pub fn new(cargo_clippy_version: String) -> Self {
+        Self {
+            version: CLIPPY_ARGS_VERSION,
+            cargo_clippy_version,
+            ..Self::default()
+        }
+    }
+
+    /// Adds the compiler arguments given after `--`, storing the lint levels apart.
+    pub fn add_rustc_args(&mut self, args: impl IntoIterator<Item = String>) {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let lint_level = match Level::split_flag(&arg) {
+                Some((level, Some(lint))) => Some(LintLevel {
+                    level,
+                    lint: lint.to_string(),
+                }),
+                Some((level, None)) => args.next().map(|lint| LintLevel { level, lint }),
+                None => None,
+            };
+            match lint_level {
+                Some(lint_level) => self.lint_levels.push(lint_level),
+                None => self.rustc_args.push(arg),
+            }
+        }
+    }
+
+    /// Returns the arguments to pass to the compiler, the lint levels coming first.
+    pub fn compiler_args(&self) -> Vec<String> {
+        self.lint_levels
+            .iter()
+            .map(|lint_level| format!("{}={}", lint_level.level.flag(), lint_level.lint))
+            .chain(self.rustc_args.iter().cloned())
+            .collect()
+    }
+
+    /// Serializes the arguments as the value of `CLIPPY_ARGS`.
+    pub fn to_env_value(&self) -> String {
+        serde_json::to_string(self).expect("the arguments can be serialized")
+    }
+
+    /// Reads the value of `CLIPPY_ARGS`, `clippy_version` being the version of the driver.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value was written by a version of `cargo-clippy` using another
+    /// format, or is invalid.
+    pub fn from_env_value(value: &str, clippy_version: &str) -> Result<Self, String> {
+        #[derive(Deserialize)]
+        struct Version {
+            version: u32,
+            #[serde(default)]
+            cargo_clippy_version: String,
+        }
+
+        let mismatch = |cargo_clippy_version: &str| {
+            let sender = if cargo_clippy_version.is_empty() {
+                "another version of `cargo-clippy`".to_string()
+            } else {
+                format!("the `cargo-clippy` of {cargo_clippy_version}")
+            };
+            format!(
+                "`CLIPPY_ARGS` was set by {sender}, which this `clippy-driver` of {clippy_version} can't read, use \
+                `cargo-clippy` and `clippy-driver` from the same toolchain"
+            )
+        };
+        match serde_json::from_str::<Version>(value) {
+            Ok(version) if version.version != CLIPPY_ARGS_VERSION => Err(mismatch(&version.cargo_clippy_version)),
+            Ok(_) => serde_json::from_str(value).map_err(|e| format!("invalid `CLIPPY_ARGS`: {e}")),
+            // Older versions separated the arguments with `__CLIPPY_HACKERY__`
+            Err(_) => Err(mismatch("")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CLIPPY_ARGS_VERSION, ChangedLines, ClippyArgs, FileChanges, Level, LintLevel};
+    use std::path::Path;
+
+    #[test]
+    fn lint_levels() {
+        let mut args = ClippyArgs::new("clippy 0.1.0".into());
+        args.add_rustc_args(
+            [
+                "-W",
+                "clippy::pedantic",
+                "-Aclippy::module_name_repetitions",
+                "--cfg",
+                "feature=\"a\"",
+            ]
+            .into_iter()
+            .chain(["--force-warn=clippy::unwrap_used", "-Dwarnings", "-Zunstable-options"])
+            .map(String::from),
+        );
+        let level = |level, lint: &str| LintLevel {
+            level,
+            lint: lint.into(),
+        };
+        assert_eq!(
+            args.lint_levels,
+            [
+                level(Level::Warn, "clippy::pedantic"),
+                level(Level::Allow, "clippy::module_name_repetitions"),
+                level(Level::ForceWarn, "clippy::unwrap_used"),
+                level(Level::Deny, "warnings"),
+            ]
+        );
+        assert_eq!(args.rustc_args, ["--cfg", "feature=\"a\"", "-Zunstable-options"]);
+        assert_eq!(
+            args.compiler_args(),
+            [
+                "--warn=clippy::pedantic",
+                "--allow=clippy::module_name_repetitions",
+                "--force-warn=clippy::unwrap_used",
+                "--deny=warnings",
+                "--cfg",
+                "feature=\"a\"",
+                "-Zunstable-options",
+            ]
+        );
+        assert_eq!(
+            ClippyArgs::from_env_value(&args.to_env_value(), "clippy 0.1.0"),
+            Ok(args)
+        );
+    }
+
+    #[test]
+    fn changed_lines() {
+        let mut changed = ChangedLines::default();
+        changed
+            .files
+            .insert("/root/src/lib.rs".into(), FileChanges::Lines(vec![(2, 3), (11, 11)]));
+        changed.files.insert("/root/src/new.rs".into(), FileChanges::All);
+        let lib = Path::new("/root/src/lib.rs");
+        assert!(changed.contains(lib, 3, 5));
+        assert!(changed.contains(lib, 1, 2));
+        assert!(changed.contains(lib, 11, 11));
+        assert!(!changed.contains(lib, 4, 10));
+        assert!(changed.contains(Path::new("/root/src/new.rs"), 1, 1));
+        assert!(!changed.contains(Path::new("/root/src/main.rs"), 1, 1));
+    }
+
+    #[test]
+    fn version_mismatch() {
+        let newer = format!(
+            r#"{{"version":{},"cargo_clippy_version":"clippy 9.9.9"}}"#,
+            CLIPPY_ARGS_VERSION + 1
+        );
+        let error = ClippyArgs::from_env_value(&newer, "clippy 0.1.0").unwrap_err();
+        assert!(error.contains("the `cargo-clippy` of clippy 9.9.9"), "{error}");
+        let error = ClippyArgs::from_env_value("--no-deps__CLIPPY_HACKERY__", "clippy 0.1.0").unwrap_err();
+        assert!(error.contains("another version of `cargo-clippy`"), "{error}");
+    }
+}
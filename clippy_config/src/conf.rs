@@ -1,25 +1,28 @@
 use crate::ClippyConfiguration;
+use crate::schema::option_schema;
 use crate::types::{
-    DisallowedPath, DisallowedPathWithoutReplacement, InherentImplLintScope, MacroMatcher, MatchLintBehaviour,
-    PubUnderscoreFieldsBehaviour, Rename, SourceItemOrdering, SourceItemOrderingCategory,
-    SourceItemOrderingModuleItemGroupings, SourceItemOrderingModuleItemKind, SourceItemOrderingTraitAssocItemKind,
-    SourceItemOrderingTraitAssocItemKinds, SourceItemOrderingWithinModuleItemGroupings,
+    CLIPPY_LINT_GROUPS, DisallowedPath, DisallowedPathWithoutReplacement, InherentImplLintScope, LintProfile,
+    MacroMatcher, MatchLintBehaviour, PubUnderscoreFieldsBehaviour, Rename, SourceItemOrdering,
+    SourceItemOrderingCategory, SourceItemOrderingModuleItemGroupings, SourceItemOrderingModuleItemKind,
+    SourceItemOrderingTraitAssocItemKind, SourceItemOrderingTraitAssocItemKinds,
+    SourceItemOrderingWithinModuleItemGroupings,
 };
 use clippy_utils::msrvs::Msrv;
 use itertools::Itertools;
-use rustc_errors::Applicability;
+use rustc_errors::{Applicability, DiagCtxtHandle};
 use rustc_session::Session;
 use rustc_span::edit_distance::edit_distance;
-use rustc_span::{BytePos, Pos, SourceFile, Span, SyntaxContext};
-use serde::de::{IgnoredAny, IntoDeserializer, MapAccess, Visitor};
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+use rustc_span::{BytePos, FileName, Pos, SourceFile, Span, SyntaxContext};
+use serde::de::{DeserializeSeed, IgnoredAny, IntoDeserializer, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::Range;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::sync::OnceLock;
-use std::{cmp, env, fmt, fs, io};
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::{cmp, env, fmt, fs, io, mem};
 
 #[rustfmt::skip]
 const DEFAULT_DOC_VALID_IDENTS: &[&str] = &[
@@ -85,6 +88,14 @@ const DEFAULT_SOURCE_ITEM_ORDERING: &[SourceItemOrderingCategory] = {
 struct TryConf {
     conf: Conf,
     value_spans: HashMap<String, Range<usize>>,
+    parent: Option<(ParentConf, Range<usize>)>,
+    /// The `paths` of an `[[overrides]]` table.
+    paths: Option<Vec<String>>,
+    /// The lints allowed by an `[[overrides]]` table, like `Lint::name_lower`, and the span of
+    /// their list.
+    allow: Vec<String>,
+    allow_span: Range<usize>,
+    overrides: Vec<TryConf>,
     errors: Vec<ConfError>,
     warnings: Vec<ConfError>,
 }
@@ -94,12 +105,177 @@ impl TryConf {
         Self {
             conf: Conf::default(),
             value_spans: HashMap::default(),
+            parent: None,
+            paths: None,
+            allow: Vec::new(),
+            allow_span: 0..0,
+            overrides: Vec::new(),
             errors: vec![ConfError::from_toml(file, error)],
             warnings: vec![],
         }
     }
 }
 
+/// Where a configuration is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConfLocation {
+    /// A `clippy.toml` or `.clippy.toml` file.
+    File(PathBuf),
+    /// The `[package.metadata.clippy]` table of a `Cargo.toml`.
+    Package(PathBuf),
+    /// The `[workspace.metadata.clippy]` table of a `Cargo.toml`.
+    Workspace(PathBuf),
+}
+
+impl ConfLocation {
+    pub fn path(&self) -> &Path {
+        let (Self::File(path) | Self::Package(path) | Self::Workspace(path)) = self;
+        path
+    }
+}
+
+impl Display for ConfLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "`{}`", path.display()),
+            Self::Package(path) => write!(f, "the `[package.metadata.clippy]` table of `{}`", path.display()),
+            Self::Workspace(path) => write!(f, "the `[workspace.metadata.clippy]` table of `{}`", path.display()),
+        }
+    }
+}
+
+/// The configuration a `clippy.toml` inherits its values from.
+#[derive(Debug)]
+enum ParentConf {
+    /// `inherit = true`: the next configuration found in the parent directories.
+    Inherit,
+    /// `extends = "path"`: the given file, relative to the directory of the current file.
+    Extends(PathBuf),
+}
+
+impl ParentConf {
+    fn resolve(&self, current: &ConfLocation) -> io::Result<(Option<ConfLocation>, Vec<String>)> {
+        let path = current.path();
+        let dir = path.parent().unwrap_or(path);
+        match self {
+            Self::Inherit => {
+                // The root package of a workspace inherits from the workspace table of the same manifest
+                if let ConfLocation::Package(path) = current
+                    && cargo_metadata_tables(path)?.1
+                {
+                    return Ok((Some(ConfLocation::Workspace(path.clone())), Vec::new()));
+                }
+                match dir.parent() {
+                    Some(parent_dir) => find_conf_file(parent_dir, false),
+                    None => Ok((None, Vec::new())),
+                }
+            },
+            Self::Extends(path) => {
+                let path = dir.join(path).canonicalize()?;
+                let location = if path.file_name() == Some("Cargo.toml".as_ref()) {
+                    ConfLocation::Workspace(path)
+                } else {
+                    ConfLocation::File(path)
+                };
+                Ok((Some(location), Vec::new()))
+            },
+        }
+    }
+}
+
+/// The options that can be set in an `[[overrides]]` table, besides `paths` and `allow`. The lints
+/// using them look them up with [`Conf::for_span`], so an option can only be added here once all of
+/// its lints do. Keep the list of `book/src/configuration.md` in sync.
+pub(crate) const OVERRIDABLE_OPTIONS: &[&str] = &[
+    "too_many_arguments_threshold",
+    "too_many_lines_threshold",
+    "type_complexity_threshold",
+    "vec_box_size_threshold",
+];
+
+/// The options of an `[[overrides]]` table, which apply to the files matching one of its `paths`.
+#[derive(Clone)]
+struct ConfOverride {
+    /// The directory of the configuration declaring the override, which `paths` are relative to.
+    root: PathBuf,
+    paths: Vec<String>,
+    /// The options set in the table until the override is resolved, the whole configuration of the
+    /// matching files afterwards.
+    conf: Conf,
+    value_spans: HashMap<String, Range<usize>>,
+    /// The lints allowed in the matching files, like `Lint::name_lower`.
+    allow: Vec<String>,
+    allow_span: Span,
+}
+
+impl ConfOverride {
+    fn matches(&self, path: &Path) -> bool {
+        let Ok(path) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        let Some(components) = path
+            .components()
+            .map(|component| component.as_os_str().to_str())
+            .collect::<Option<Vec<_>>>()
+        else {
+            return false;
+        };
+        self.paths.iter().any(|pattern| {
+            let pattern = pattern
+                .split('/')
+                .filter(|component| !matches!(*component, "" | "."))
+                .collect::<Vec<_>>();
+            glob_matches(&pattern, &components)
+        })
+    }
+}
+
+/// Matches the components of a path against the ones of a glob pattern. `**` matches any number of
+/// components, `*` and `?` match any sequence of characters and any character within a component.
+fn glob_matches(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skipped| glob_matches(rest, &path[skipped..])),
+        Some((component_pattern, rest)) => path.split_first().is_some_and(|(component, path)| {
+            component_matches(component_pattern, component) && glob_matches(rest, path)
+        }),
+    }
+}
+
+pub(crate) fn component_matches(pattern: &str, component: &str) -> bool {
+    let mut chars = pattern.chars();
+    match chars.next() {
+        None => component.is_empty(),
+        Some('*') => component
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([component.len()])
+            .any(|i| component_matches(chars.as_str(), &component[i..])),
+        Some('?') => {
+            let mut component = component.chars();
+            component.next().is_some() && component_matches(chars.as_str(), component.as_str())
+        },
+        Some(c) => component
+            .strip_prefix(c)
+            .is_some_and(|component| component_matches(chars.as_str(), component)),
+    }
+}
+
+/// Where the value of an option comes from, shown by `cargo clippy --print-config`.
+#[derive(Clone)]
+enum ValueOrigin {
+    /// The option is set in a configuration file, at this span.
+    Conf(Span),
+    /// The MSRV is the `rust-version` of the package.
+    RustVersion,
+}
+
+fn serialize_value(value: &impl Serialize) -> Option<String> {
+    let mut text = String::new();
+    value.serialize(toml::ser::ValueSerializer::new(&mut text)).ok()?;
+    Some(text)
+}
+
 #[derive(Debug)]
 struct ConfError {
     message: String,
@@ -173,11 +349,83 @@ macro_rules! default_text {
     };
 }
 
+macro_rules! default_json {
+    ($value:expr) => {
+        serde_json::to_value($value).ok()
+    };
+    ($value:expr, $override:expr) => {
+        None
+    };
+}
+
+/// A value of the configuration along with its span, if it has one.
+///
+/// Unlike `toml::Spanned`, it accepts the tables which are only declared by the headers of their
+/// subtables, like `profiles` in `[profiles.library]`, which have no span in `toml` 0.7.
+struct SpannedValue {
+    value: toml::Value,
+    span: Option<Range<usize>>,
+}
+
+impl<'de> Deserialize<'de> for SpannedValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // The names `toml::Spanned` is deserialized with, which make `toml` give the span along
+        // with the value when there is one.
+        const NAME: &str = "$__serde_spanned_private_Spanned";
+        const START_FIELD: &str = "$__serde_spanned_private_start";
+        const END_FIELD: &str = "$__serde_spanned_private_end";
+        const VALUE_FIELD: &str = "$__serde_spanned_private_value";
+
+        struct SpannedValueVisitor;
+
+        impl<'de> Visitor<'de> for SpannedValueVisitor {
+            type Value = SpannedValue;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a TOML value")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut table = toml::Table::new();
+                let Some(key) = map.next_key::<String>()? else {
+                    return Ok(SpannedValue {
+                        value: toml::Value::Table(table),
+                        span: None,
+                    });
+                };
+                if key == START_FIELD {
+                    let start = map.next_value()?;
+                    map.next_key::<IgnoredAny>()?;
+                    let end = map.next_value()?;
+                    map.next_key::<IgnoredAny>()?;
+                    let value = map.next_value()?;
+                    return Ok(SpannedValue {
+                        value,
+                        span: Some(start..end),
+                    });
+                }
+                // A table without a span, given as is
+                table.insert(key, map.next_value()?);
+                while let Some((key, value)) = map.next_entry()? {
+                    table.insert(key, value);
+                }
+                Ok(SpannedValue {
+                    value: toml::Value::Table(table),
+                    span: None,
+                })
+            }
+        }
+
+        deserializer.deserialize_struct(NAME, &[START_FIELD, END_FIELD, VALUE_FIELD], SpannedValueVisitor)
+    }
+}
+
 macro_rules! deserialize {
-    ($map:expr, $ty:ty, $errors:expr, $file:expr) => {{
-        let raw_value = $map.next_value::<toml::Spanned<toml::Value>>()?;
-        let value_span = raw_value.span();
-        let value = match <$ty>::deserialize(raw_value.into_inner()) {
+    ($map:expr, $ty:ty, $errors:expr, $file:expr, $key:expr) => {{
+        let raw_value = $map.next_value::<SpannedValue>()?;
+        // Point to the key of the tables without a span
+        let value_span = raw_value.span.unwrap_or_else(|| $key.span());
+        let value = match <$ty>::deserialize(raw_value.value) {
             Err(e) => {
                 $errors.push(ConfError::spanned(
                     $file,
@@ -192,7 +440,7 @@ macro_rules! deserialize {
         (value, value_span)
     }};
 
-    ($map:expr, $ty:ty, $errors:expr, $file:expr, $replacements_allowed:expr) => {{
+    ($map:expr, $ty:ty, $errors:expr, $file:expr, $key:expr, $replacements_allowed:expr, $name:expr) => {{
         let array = $map.next_value::<Vec<toml::Spanned<toml::Value>>>()?;
         let mut disallowed_paths_span = Range {
             start: usize::MAX,
@@ -214,6 +462,24 @@ macro_rules! deserialize {
                 },
                 Ok(disallowed_path) => disallowed_path,
             };
+            if disallowed_path.has_for_types() && !matches!($name, "disallowed_methods" | "disallowed_trait_impls") {
+                $errors.push(ConfError::spanned(
+                    $file,
+                    "`for-types` can only be used in `disallowed-methods` and `disallowed-trait-impls`",
+                    None,
+                    value_span,
+                ));
+                continue;
+            }
+            if disallowed_path.has_allowed_in() && !$name.starts_with("disallowed_") {
+                $errors.push(ConfError::spanned(
+                    $file,
+                    "`allowed-in` can only be used in the `disallowed-*` options",
+                    None,
+                    value_span,
+                ));
+                continue;
+            }
             disallowed_paths_span = union(&disallowed_paths_span, &value_span);
             disallowed_path.set_span(span_from_toml_range($file, value_span));
             disallowed_paths.push(disallowed_path);
@@ -232,8 +498,13 @@ macro_rules! define_Conf {
         $name:ident: $ty:ty = $default:expr,
     )*) => {
         /// Clippy lint configuration
+        #[derive(Clone)]
         pub struct Conf {
             $($(#[cfg_attr(doc, doc = $doc)])+ pub $name: $ty,)*
+            /// The `[[overrides]]` tables, see [`Conf::for_span`].
+            overrides: Vec<ConfOverride>,
+            /// Where the options that aren't set to their default value come from.
+            origins: HashMap<String, ValueOrigin>,
         }
 
         mod defaults {
@@ -243,16 +514,40 @@ macro_rules! define_Conf {
 
         impl Default for Conf {
             fn default() -> Self {
-                Self { $($name: defaults::$name(),)* }
+                Self {
+                    $($name: defaults::$name(),)*
+                    overrides: Vec::new(),
+                    origins: HashMap::new(),
+                }
+            }
+        }
+
+        impl Conf {
+            /// Replaces the value of every option that wasn't set in the configuration file with the one
+            /// from `base`.
+            fn inherit_unset(&mut self, base: Conf, value_spans: &HashMap<String, Range<usize>>) {
+                $(if !value_spans.contains_key(stringify!($name)) {
+                    self.$name = base.$name;
+                })*
+            }
+
+            /// Returns the name of every option along with its value serialized as TOML, or `None`
+            /// if the value can't be represented in TOML.
+            pub fn serialized_values(&self) -> Vec<(&'static str, Option<String>)> {
+                vec![$((stringify!($name), serialize_value(&self.$name)),)*]
             }
         }
 
         #[derive(Deserialize)]
         #[serde(field_identifier, rename_all = "kebab-case")]
         #[expect(non_camel_case_types)]
-        enum Field { $($name,)* third_party, }
+        enum Field { $($name,)* third_party, inherit, extends, overrides, paths, allow, }
 
-        struct ConfVisitor<'a>(&'a SourceFile);
+        struct ConfVisitor<'a> {
+            file: &'a SourceFile,
+            /// Whether the visited table is an `[[overrides]]` table.
+            in_override: bool,
+        }
 
         impl<'de> Visitor<'de> for ConfVisitor<'_> {
             type Value = TryConf;
@@ -263,6 +558,11 @@ macro_rules! define_Conf {
 
             fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error> where V: MapAccess<'de> {
                 let mut value_spans = HashMap::new();
+                let mut parent = None;
+                let mut paths = None;
+                let mut allow = Vec::new();
+                let mut allow_span = 0..0;
+                let mut overrides = Vec::new();
                 let mut errors = Vec::new();
                 let mut warnings = Vec::new();
 
@@ -274,41 +574,89 @@ macro_rules! define_Conf {
                     let field = match Field::deserialize(name.get_ref().as_str().into_deserializer()) {
                         Err(e) => {
                             let e: FieldError = e;
-                            errors.push(ConfError::spanned(self.0, e.error, e.suggestion, name.span()));
+                            errors.push(ConfError::spanned(self.file, e.error, e.suggestion, name.span()));
                             continue;
                         }
                         Ok(field) => field
                     };
 
+                    let misplaced = match field {
+                        Field::paths | Field::allow => !self.in_override,
+                        Field::inherit | Field::extends | Field::overrides => self.in_override,
+                        _ => false,
+                    };
+                    if misplaced {
+                        let message = if self.in_override {
+                            format!("`{}` can't be used in `[[overrides]]`", name.get_ref())
+                        } else {
+                            format!("`{}` can only be used in `[[overrides]]`", name.get_ref())
+                        };
+                        errors.push(ConfError::spanned(self.file, message, None, name.span()));
+                        map.next_value::<IgnoredAny>()?;
+                        continue;
+                    }
+
                     match field {
                         $(Field::$name => {
                             // Is this a deprecated field, i.e., is `$dep` set? If so, push a warning.
-                            $(warnings.push(ConfError::spanned(self.0, format!("deprecated field `{}`. {}", name.get_ref(), $dep), None, name.span()));)?
-                            let (value, value_span) =
-                                deserialize!(map, $ty, errors, self.0 $(, $replacements_allowed)?);
+                            $(warnings.push(ConfError::spanned(self.file, format!("deprecated field `{}`. {}", name.get_ref(), $dep), None, name.span()));)?
+                            let (value, value_span) = deserialize!(
+                                map, $ty, errors, self.file, name $(, $replacements_allowed, stringify!($name))?
+                            );
                             // Was this field set previously?
                             if $name.is_some() {
-                                errors.push(ConfError::spanned(self.0, format!("duplicate field `{}`", name.get_ref()), None, name.span()));
+                                errors.push(ConfError::spanned(self.file, format!("duplicate field `{}`", name.get_ref()), None, name.span()));
                                 continue;
                             }
                             $name = Some(value);
-                            value_spans.insert(name.get_ref().as_str().to_string(), value_span);
                             // If this is a deprecated field, was the new field (`$new_conf`) set previously?
                             // Note that `$new_conf` is one of the defined `$name`s.
                             $(match $new_conf {
-                                Some(_) => errors.push(ConfError::spanned(self.0, concat!(
+                                Some(_) => errors.push(ConfError::spanned(self.file, concat!(
                                     "duplicate field `", stringify!($new_conf),
                                     "` (provided as `", stringify!($name), "`)"
                                 ), None, name.span())),
-                                None => $new_conf = $name.clone(),
+                                None => {
+                                    $new_conf = $name.clone();
+                                    value_spans.insert(stringify!($new_conf).to_string(), value_span.clone());
+                                },
                             })?
+                            value_spans.insert(stringify!($name).to_string(), value_span);
                         })*
                         // ignore contents of the third_party key
-                        Field::third_party => drop(map.next_value::<IgnoredAny>())
+                        Field::third_party => drop(map.next_value::<IgnoredAny>()),
+                        Field::inherit => {
+                            let (inherit, value_span) = deserialize!(map, bool, errors, self.file, name);
+                            if inherit {
+                                set_parent(self.file, &mut parent, &mut errors, ParentConf::Inherit, value_span);
+                            }
+                        },
+                        Field::extends => {
+                            let (path, value_span) = deserialize!(map, PathBuf, errors, self.file, name);
+                            set_parent(self.file, &mut parent, &mut errors, ParentConf::Extends(path), value_span);
+                        },
+                        Field::overrides => {
+                            for mut conf_override in map.next_value_seed(OverridesSeed(self.file))? {
+                                check_override(self.file, &mut conf_override, name.span());
+                                errors.append(&mut conf_override.errors);
+                                warnings.append(&mut conf_override.warnings);
+                                overrides.push(conf_override);
+                            }
+                        },
+                        Field::paths => paths = Some(deserialize!(map, Vec<String>, errors, self.file, name).0),
+                        Field::allow => {
+                            let (lints, value_span) = deserialize!(map, Vec<String>, errors, self.file, name);
+                            allow = check_override_allow(self.file, lints, value_span.clone(), &mut errors);
+                            allow_span = value_span;
+                        },
                     }
                 }
-                let conf = Conf { $($name: $name.unwrap_or_else(defaults::$name),)* };
-                Ok(TryConf { conf, value_spans, errors, warnings })
+                let conf = Conf {
+                    $($name: $name.unwrap_or_else(defaults::$name),)*
+                    overrides: Vec::new(),
+                    origins: HashMap::new(),
+                };
+                Ok(TryConf { conf, value_spans, parent, paths, allow, allow_span, overrides, errors, warnings })
             }
         }
 
@@ -319,13 +667,33 @@ macro_rules! define_Conf {
                     default: default_text!(defaults::$name() $(, $default_text)?),
                     lints: &[$($(stringify!($for_lints)),*)?],
                     doc: concat!($($doc, '\n',)*),
-                    deprecation_reason: wrap_option!($($dep)?)
+                    deprecation_reason: wrap_option!($($dep)?),
+                    schema: option_schema::<$ty>(default_json!(defaults::$name() $(, $default_text)?)),
                 },
             )*]
         }
     };
 }
 
+fn set_parent(
+    file: &SourceFile,
+    parent: &mut Option<(ParentConf, Range<usize>)>,
+    errors: &mut Vec<ConfError>,
+    value: ParentConf,
+    span: Range<usize>,
+) {
+    if parent.is_some() {
+        errors.push(ConfError::spanned(
+            file,
+            "only one of `inherit` and `extends` can be used",
+            None,
+            span,
+        ));
+    } else {
+        *parent = Some((value, span));
+    }
+}
+
 fn union(x: &Range<usize>, y: &Range<usize>) -> Range<usize> {
     Range {
         start: cmp::min(x.start, y.start),
@@ -618,33 +986,49 @@ define_Conf! {
     /// The list of disallowed fields, written as fully qualified paths.
     ///
     /// **Fields:**
-    /// - `path` (required): the fully qualified path to the field that should be disallowed
+    /// - `path` (required): the fully qualified path to the field that should be disallowed,
+    ///   where the last segment can contain the `*` and `?` wildcards to disallow several fields at once
     /// - `reason` (optional): explanation why this field is disallowed
     /// - `replacement` (optional): suggested alternative method
     /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
     ///   if the path doesn't exist, instead of emitting an error
+    /// - `allowed-in` (optional): the crates and modules where the field can be used anyway, as paths starting
+    ///   with a crate name or `crate`. A module path also allows the modules it contains,
+    ///   with or without a trailing `::*`
     #[disallowed_paths_allow_replacements = true]
     #[lints(disallowed_fields)]
     disallowed_fields: Vec<DisallowedPath> = Vec::new(),
     /// The list of disallowed macros, written as fully qualified paths.
     ///
     /// **Fields:**
-    /// - `path` (required): the fully qualified path to the macro that should be disallowed
+    /// - `path` (required): the fully qualified path to the macro that should be disallowed,
+    ///   where the last segment can contain the `*` and `?` wildcards to disallow several macros at once
     /// - `reason` (optional): explanation why this macro is disallowed
     /// - `replacement` (optional): suggested alternative macro
     /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
     ///   if the path doesn't exist, instead of emitting an error
+    /// - `allowed-in` (optional): the crates and modules where the macro can be used anyway, as paths starting
+    ///   with a crate name or `crate`. A module path also allows the modules it contains,
+    ///   with or without a trailing `::*`
     #[disallowed_paths_allow_replacements = true]
     #[lints(disallowed_macros)]
     disallowed_macros: Vec<DisallowedPath> = Vec::new(),
     /// The list of disallowed methods, written as fully qualified paths.
     ///
     /// **Fields:**
-    /// - `path` (required): the fully qualified path to the method that should be disallowed
+    /// - `path` (required): the fully qualified path to the method that should be disallowed,
+    ///   where the last segment can contain the `*` and `?` wildcards to disallow several methods at once
     /// - `reason` (optional): explanation why this method is disallowed
     /// - `replacement` (optional): suggested alternative method
     /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
     ///   if the path doesn't exist, instead of emitting an error
+    /// - `allowed-in` (optional): the crates and modules where the method can be used anyway, as paths starting
+    ///   with a crate name or `crate`. A module path also allows the modules it contains,
+    ///   with or without a trailing `::*`
+    /// - `for-types` (optional): the types the method is disallowed with, as fully qualified paths starting with a
+    ///   crate name or `crate` that can contain the `*` and `?` wildcards. A call is disallowed when one of the types
+    ///   of its generic arguments is one of them, like the implementor of a trait method or the type returned by
+    ///   `Iterator::collect`. When it's not set, the method is disallowed with any type
     #[disallowed_paths_allow_replacements = true]
     #[lints(disallowed_methods)]
     disallowed_methods: Vec<DisallowedPath> = Vec::new(),
@@ -653,14 +1037,36 @@ define_Conf! {
     /// default configuration of Clippy. By default, any configuration will replace the default value.
     #[lints(disallowed_names)]
     disallowed_names: Vec<String> = DEFAULT_DISALLOWED_NAMES.iter().map(ToString::to_string).collect(),
+    /// The list of disallowed trait implementations, written as fully qualified paths to the traits.
+    /// Both manual implementations and derives are linted.
+    ///
+    /// **Fields:**
+    /// - `path` (required): the fully qualified path to the trait that shouldn't be implemented,
+    ///   where the last segment can contain the `*` and `?` wildcards to disallow several traits at once
+    /// - `reason` (optional): explanation why this trait shouldn't be implemented
+    /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
+    ///   if the path doesn't exist, instead of emitting an error
+    /// - `allowed-in` (optional): the crates and modules where the trait can be implemented anyway, as paths
+    ///   starting with a crate name or `crate`. A module path also allows the modules it contains,
+    ///   with or without a trailing `::*`
+    /// - `for-types` (optional): the types for which the trait shouldn't be implemented, as fully qualified
+    ///   paths starting with a crate name or `crate` that can contain the `*` and `?` wildcards. When it's
+    ///   not set, the trait shouldn't be implemented for any type
+    #[disallowed_paths_allow_replacements = false]
+    #[lints(disallowed_trait_impls)]
+    disallowed_trait_impls: Vec<DisallowedPathWithoutReplacement> = Vec::new(),
     /// The list of disallowed types, written as fully qualified paths.
     ///
     /// **Fields:**
-    /// - `path` (required): the fully qualified path to the type that should be disallowed
+    /// - `path` (required): the fully qualified path to the type that should be disallowed,
+    ///   where the last segment can contain the `*` and `?` wildcards to disallow several types at once
     /// - `reason` (optional): explanation why this type is disallowed
     /// - `replacement` (optional): suggested alternative type
     /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
     ///   if the path doesn't exist, instead of emitting an error
+    /// - `allowed-in` (optional): the crates and modules where the type can be used anyway, as paths starting
+    ///   with a crate name or `crate`. A module path also allows the modules it contains,
+    ///   with or without a trailing `::*`
     #[disallowed_paths_allow_replacements = true]
     #[lints(disallowed_types)]
     disallowed_types: Vec<DisallowedPath> = Vec::new(),
@@ -865,6 +1271,22 @@ define_Conf! {
     /// The minimum size (in bytes) to consider a type for passing by reference instead of by value.
     #[lints(large_types_passed_by_value)]
     pass_by_value_size_limit: u64 = 256,
+    /// Named sets of lint levels, selected with `cargo clippy --lint-profile <NAME>`. Each profile
+    /// lists the lints or lint groups to `allow`, `warn`, `deny` or `forbid`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// [profiles.library]
+    /// warn = ["clippy::missing_errors_doc", "clippy::missing_panics_doc"]
+    /// deny = ["clippy::unwrap_used"]
+    /// ```
+    ///
+    /// #### Noteworthy
+    ///
+    /// - The profiles of an inherited configuration are kept, unless one of the same name replaces
+    /// them.
+    profiles: BTreeMap<String, LintProfile> = BTreeMap::new(),
     /// Lint "public" fields in a struct that are prefixed with an underscore based on their
     /// exported visibility, or whether they are marked as "pub".
     #[lints(pub_underscore_fields)]
@@ -946,6 +1368,11 @@ define_Conf! {
     /// Whether to also emit warnings for unsafe blocks with metavariable expansions in **private** macros.
     #[lints(macro_metavars_in_unsafe)]
     warn_unsafe_macro_metavars_in_private_macros: bool = false,
+    /// Whether to warn about the entries of `disallowed-fields`, `disallowed-macros`, `disallowed-methods`,
+    /// `disallowed-trait-impls` and `disallowed-types` that refer to an item but are never used in the linted
+    /// crate.
+    #[lints(disallowed_fields, disallowed_macros, disallowed_methods, disallowed_trait_impls, disallowed_types)]
+    warn_unused_disallowed_paths: bool = false,
 }
 
 /// Search for the configuration file.
@@ -953,17 +1380,38 @@ define_Conf! {
 /// # Errors
 ///
 /// Returns any unexpected filesystem error encountered when searching for the config file
-pub fn lookup_conf_file() -> io::Result<(Option<PathBuf>, Vec<String>)> {
-    /// Possible filename to search for.
-    const CONFIG_FILE_NAMES: [&str; 2] = [".clippy.toml", "clippy.toml"];
+pub fn lookup_conf_file() -> io::Result<(Option<ConfLocation>, Vec<String>)> {
+    lookup_conf_file_in(env::var_os("CLIPPY_CONF_DIR").map(PathBuf::from))
+}
+
+/// Like [`lookup_conf_file`], but starts in `conf_dir` instead of `CLIPPY_CONF_DIR`, e.g. the
+/// directory given to `cargo clippy --conf-dir`.
+///
+/// # Errors
+///
+/// Returns any unexpected filesystem error encountered when searching for the config file
+pub fn lookup_conf_file_in(conf_dir: Option<PathBuf>) -> io::Result<(Option<ConfLocation>, Vec<String>)> {
+    // `[package.metadata.clippy]` only applies to the package being linted, which isn't the one in
+    // `conf_dir`.
+    let include_package = conf_dir.is_none();
 
-    // Start looking for a config file in CLIPPY_CONF_DIR, or failing that, CARGO_MANIFEST_DIR.
+    // Start looking for a config file in `conf_dir`, or failing that, CARGO_MANIFEST_DIR.
     // If neither of those exist, use ".". (Update documentation if this priority changes)
-    let mut current = env::var_os("CLIPPY_CONF_DIR")
-        .or_else(|| env::var_os("CARGO_MANIFEST_DIR"))
-        .map_or_else(|| PathBuf::from("."), PathBuf::from)
-        .canonicalize()?;
+    let current = conf_dir
+        .or_else(|| env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    find_conf_file(&current, include_package)
+}
 
+/// Search for a configuration in `current` and its parent directories, either a configuration file
+/// or a `[workspace.metadata.clippy]` table in a `Cargo.toml`. `[package.metadata.clippy]` tables
+/// are only considered in `current` if `include_package` is set.
+fn find_conf_file(current: &Path, mut include_package: bool) -> io::Result<(Option<ConfLocation>, Vec<String>)> {
+    /// Possible filename to search for.
+    const CONFIG_FILE_NAMES: [&str; 2] = [".clippy.toml", "clippy.toml"];
+
+    let mut current = current.canonicalize()?;
     let mut found_config: Option<PathBuf> = None;
     let mut warnings = vec![];
 
@@ -990,8 +1438,27 @@ pub fn lookup_conf_file() -> io::Result<(Option<PathBuf>, Vec<String>)> {
             }
         }
 
-        if found_config.is_some() {
-            return Ok((found_config, warnings));
+        let manifest = current.join("Cargo.toml");
+        let (package, workspace) = cargo_metadata_tables(&manifest)?;
+        let package = package && include_package;
+        include_package = false;
+
+        if let Some(found_config) = found_config {
+            if package || workspace {
+                return Err(io::Error::other(format!(
+                    "`{}` and the `[{}.metadata.clippy]` table of `{}` both configure Clippy, only one of them can be used",
+                    found_config.display(),
+                    if package { "package" } else { "workspace" },
+                    manifest.display(),
+                )));
+            }
+            return Ok((Some(ConfLocation::File(found_config)), warnings));
+        }
+        if package {
+            return Ok((Some(ConfLocation::Package(manifest)), warnings));
+        }
+        if workspace {
+            return Ok((Some(ConfLocation::Workspace(manifest)), warnings));
         }
 
         // If the current directory has no parent, we're done searching.
@@ -1001,76 +1468,571 @@ pub fn lookup_conf_file() -> io::Result<(Option<PathBuf>, Vec<String>)> {
     }
 }
 
+/// Reads the lint profile `name` from the configuration at `location`, as found by
+/// [`lookup_conf_file`], or from the ones it inherits from, the first definition of the profile
+/// taking precedence like in [`Conf::read`].
+///
+/// `clippy-driver` needs the profile before creating the compiler session, so the configuration is
+/// read without one. Its errors are reported by [`Conf::read`] once the session is created, an
+/// invalid profile is then missing here.
+pub fn read_lint_profile(name: &str, location: &ConfLocation) -> Option<LintProfile> {
+    rustc_span::create_default_session_globals_then(|| {
+        let source_map = SourceMap::new(FilePathMapping::empty());
+        let mut conf = read_conf_file(&source_map, None, location, &mut Vec::new());
+        conf.conf.profiles.remove(name)
+    })
+}
+
+/// Returns whether the manifest at `path` has a `[package.metadata.clippy]` and a
+/// `[workspace.metadata.clippy]` table.
+fn cargo_metadata_tables(path: &Path) -> io::Result<(bool, bool)> {
+    let src = match fs::read_to_string(path) {
+        Ok(src) => src,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok((false, false)),
+        Err(e) => return Err(e),
+    };
+    // An invalid manifest is reported by Cargo itself
+    let Ok(manifest) = toml::from_str::<toml::Table>(&src) else {
+        return Ok((false, false));
+    };
+    let has_table = |key: &str| {
+        manifest
+            .get(key)
+            .and_then(|table| table.get("metadata"))
+            .and_then(|metadata| metadata.get("clippy"))
+            .is_some_and(toml::Value::is_table)
+    };
+    Ok((has_table("package"), has_table("workspace")))
+}
+
 fn deserialize(file: &SourceFile) -> TryConf {
-    match toml::de::Deserializer::new(file.src.as_ref().unwrap()).deserialize_map(ConfVisitor(file)) {
-        Ok(mut conf) => {
-            extend_vec_if_indicator_present(&mut conf.conf.disallowed_names, DEFAULT_DISALLOWED_NAMES);
-            extend_vec_if_indicator_present(&mut conf.conf.allowed_prefixes, DEFAULT_ALLOWED_PREFIXES);
-            extend_vec_if_indicator_present(
-                &mut conf.conf.allow_renamed_params_for,
-                DEFAULT_ALLOWED_TRAITS_WITH_RENAMED_PARAMS,
-            );
+    let visitor = ConfVisitor {
+        file,
+        in_override: false,
+    };
+    match toml::de::Deserializer::new(file.src.as_ref().unwrap()).deserialize_map(visitor) {
+        Ok(conf) => conf,
+        Err(e) => TryConf::from_toml_error(file, &e),
+    }
+}
 
-            // Confirms that the user has not accidentally configured ordering requirements for groups that
-            // aren't configured.
-            if let SourceItemOrderingWithinModuleItemGroupings::Custom(groupings) =
-                &conf.conf.module_items_ordered_within_groupings
+/// Deserializes the `[<table>.metadata.clippy]` table of a `Cargo.toml`, `table` being either
+/// `package` or `workspace`.
+fn deserialize_cargo_table(file: &SourceFile, table: &'static str) -> TryConf {
+    let seed = KeySeed {
+        key: table,
+        seed: KeySeed {
+            key: "metadata",
+            seed: KeySeed {
+                key: "clippy",
+                seed: ConfVisitor {
+                    file,
+                    in_override: false,
+                },
+            },
+        },
+    };
+    match seed.deserialize(toml::de::Deserializer::new(file.src.as_ref().unwrap())) {
+        Ok(conf) => conf.flatten().flatten().unwrap_or_default(),
+        Err(e) => TryConf::from_toml_error(file, &e),
+    }
+}
+
+/// Deserializes the configuration in a nested table, keeping the spans relative to the whole file.
+impl<'de> DeserializeSeed<'de> for ConfVisitor<'_> {
+    type Value = TryConf;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+/// Deserializes the `[[overrides]]` tables.
+struct OverridesSeed<'a>(&'a SourceFile);
+
+impl<'de> DeserializeSeed<'de> for OverridesSeed<'_> {
+    type Value = Vec<TryConf>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for OverridesSeed<'_> {
+    type Value = Vec<TryConf>;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an array of tables")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut overrides = Vec::new();
+        while let Some(conf_override) = seq.next_element_seed(ConfVisitor {
+            file: self.0,
+            in_override: true,
+        })? {
+            overrides.push(conf_override);
+        }
+        Ok(overrides)
+    }
+}
+
+/// Checks that an `[[overrides]]` table has `paths` and only sets options listed in
+/// `OVERRIDABLE_OPTIONS`. `span` is the one of the `overrides` key.
+fn check_override(file: &SourceFile, conf: &mut TryConf, span: Range<usize>) {
+    if conf.paths.is_none() {
+        conf.errors
+            .push(ConfError::spanned(file, "missing field `paths`", None, span));
+    }
+    let mut value_spans = conf
+        .value_spans
+        .iter()
+        .filter(|(name, _)| !OVERRIDABLE_OPTIONS.contains(&name.as_str()))
+        .collect::<Vec<_>>();
+    value_spans.sort_by_key(|&(name, span)| (span.start, name));
+    for (name, span) in value_spans {
+        let message = format!("`{}` can't be set in `[[overrides]]`", name.replace('_', "-"));
+        conf.errors.push(ConfError::spanned(file, message, None, span.clone()));
+    }
+}
+
+/// Checks the lints of the `allow` key of an `[[overrides]]` table and returns their names like
+/// `Lint::name_lower`. They're allowed when they're emitted, so only Clippy's own lints can be
+/// allowed, not the lint groups.
+fn check_override_allow(
+    file: &SourceFile,
+    lints: Vec<String>,
+    span: Range<usize>,
+    errors: &mut Vec<ConfError>,
+) -> Vec<String> {
+    let mut allow = Vec::new();
+    for lint in lints {
+        let name = lint.to_lowercase().replace('-', "_");
+        let message = match name.strip_prefix("clippy::") {
+            None => format!("`{lint}` isn't one of Clippy's lints, which are written like `clippy::unwrap_used`"),
+            Some(group) if CLIPPY_LINT_GROUPS.contains(&group) => {
+                format!("the lint group `{lint}` can't be allowed in `[[overrides]]`, only single lints")
+            },
+            Some(_) => {
+                allow.push(name);
+                continue;
+            },
+        };
+        errors.push(ConfError::spanned(file, message, None, span.clone()));
+    }
+    allow
+}
+
+/// Deserializes the value of `key` in a table with `seed`, ignoring every other key.
+struct KeySeed<S> {
+    key: &'static str,
+    seed: S,
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for KeySeed<S> {
+    type Value = Option<S::Value>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, S: DeserializeSeed<'de>> Visitor<'de> for KeySeed<S> {
+    type Value = Option<S::Value>;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "a table with a `{}` key", self.key)
+    }
+
+    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let Self { key, seed } = self;
+        let mut seed = Some(seed);
+        let mut value = None;
+        while let Some(name) = map.next_key::<String>()? {
+            if name == key
+                && let Some(seed) = seed.take()
             {
-                for grouping in groupings {
-                    if !conf.conf.module_item_order_groupings.is_grouping(grouping) {
-                        // Since this isn't fixable by rustfix, don't emit a `Suggestion`. This just adds some useful
-                        // info for the user instead.
-
-                        let names = conf.conf.module_item_order_groupings.grouping_names();
-                        let suggestion = suggest_candidate(grouping, names.iter().map(String::as_str))
-                            .map(|s| format!(" perhaps you meant `{s}`?"))
-                            .unwrap_or_default();
-                        let names = names.iter().map(|s| format!("`{s}`")).join(", ");
-                        let message = format!(
-                            "unknown ordering group: `{grouping}` was not specified in `module-items-ordered-within-groupings`,{suggestion} expected one of: {names}"
-                        );
-
-                        let span = conf
-                            .value_spans
-                            .get("module_item_order_groupings")
-                            .cloned()
-                            .unwrap_or_default();
-                        conf.errors.push(ConfError::spanned(file, message, None, span));
-                    }
-                }
+                value = Some(map.next_value_seed(seed)?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
             }
+        }
+        Ok(value)
+    }
+}
 
-            // TODO: THIS SHOULD BE TESTED, this comment will be gone soon
-            if conf.conf.allowed_idents_below_min_chars.iter().any(|e| e == "..") {
-                conf.conf
-                    .allowed_idents_below_min_chars
-                    .extend(DEFAULT_ALLOWED_IDENTS_BELOW_MIN_CHARS.iter().map(ToString::to_string));
-            }
-            if conf.conf.doc_valid_idents.iter().any(|e| e == "..") {
-                conf.conf
-                    .doc_valid_idents
-                    .extend(DEFAULT_DOC_VALID_IDENTS.iter().map(ToString::to_string));
+/// Reads the configuration at `location`, along with any configuration it inherits from through
+/// `inherit` or `extends`. `chain` holds the configurations already being read, to detect cycles.
+///
+/// The errors are returned in the [`TryConf`], except for the ones that have no span in a
+/// configuration file, which are reported to `dcx` if there is one.
+fn read_conf_file(
+    source_map: &SourceMap,
+    dcx: Option<DiagCtxtHandle<'_>>,
+    location: &ConfLocation,
+    chain: &mut Vec<ConfLocation>,
+) -> TryConf {
+    let path = location.path();
+    let file = match source_map.load_file(path) {
+        Ok(file) => file,
+        Err(error) => {
+            if let Some(dcx) = dcx {
+                dcx.err(format!("failed to read `{}`: {error}", path.display()));
             }
+            return TryConf::default();
+        },
+    };
+    chain.push(location.clone());
+
+    let mut conf = match location {
+        ConfLocation::File(_) => deserialize(&file),
+        ConfLocation::Package(_) => deserialize_cargo_table(&file, "package"),
+        ConfLocation::Workspace(_) => deserialize_cargo_table(&file, "workspace"),
+    };
+    let base = if let Some((parent, span)) = conf.parent.take() {
+        match parent.resolve(location) {
+            Ok((Some(parent), _)) if chain.contains(&parent) => {
+                let message = format!("{parent} is already part of the inheritance chain");
+                conf.errors.push(ConfError::spanned(&file, message, None, span));
+                None
+            },
+            Ok((Some(parent), warnings)) => {
+                if let Some(dcx) = dcx {
+                    for warning in warnings {
+                        dcx.warn(warning);
+                    }
+                }
+                Some(read_conf_file(source_map, dcx, &parent, chain))
+            },
+            Ok((None, _)) => {
+                let message = "no configuration file found in the parent directories to inherit from";
+                conf.errors.push(ConfError::spanned(&file, message, None, span));
+                None
+            },
+            Err(error) => {
+                let message = format!("failed to find the configuration file to inherit from: {error}");
+                conf.errors.push(ConfError::spanned(&file, message, None, span));
+                None
+            },
+        }
+    } else {
+        None
+    };
 
-            conf
+    let mut base = match base {
+        Some(parent) => {
+            // Diagnostics of the parent files point into those files, so they're reported as is.
+            conf.errors.extend(parent.errors);
+            conf.warnings.extend(parent.warnings);
+            parent.conf
         },
-        Err(e) => TryConf::from_toml_error(file, &e),
+        None => Conf::default(),
+    };
+    let inherited_overrides = mem::take(&mut base.overrides);
+    let mut origins = mem::take(&mut base.origins);
+    conf.conf.merge(base, &conf.value_spans);
+    check_module_item_order_groupings(&file, &mut conf);
+    origins.extend(value_origins(&file, &conf.value_spans));
+    conf.conf.origins = origins;
+
+    // The overrides of this file take precedence over the inherited ones.
+    let root = path.parent().unwrap_or(path);
+    conf.conf.overrides = mem::take(&mut conf.overrides)
+        .into_iter()
+        .filter_map(|conf_override| {
+            let mut override_conf = conf_override.conf;
+            override_conf.origins = value_origins(&file, &conf_override.value_spans).collect();
+            Some(ConfOverride {
+                root: root.to_path_buf(),
+                paths: conf_override.paths?,
+                conf: override_conf,
+                value_spans: conf_override.value_spans,
+                allow: conf_override.allow,
+                allow_span: span_from_toml_range(&file, conf_override.allow_span),
+            })
+        })
+        .chain(inherited_overrides)
+        .collect();
+
+    chain.pop();
+    conf
+}
+
+fn value_origins<'a>(
+    file: &'a SourceFile,
+    value_spans: &'a HashMap<String, Range<usize>>,
+) -> impl Iterator<Item = (String, ValueOrigin)> + 'a {
+    value_spans.iter().map(|(name, span)| {
+        (
+            name.clone(),
+            ValueOrigin::Conf(span_from_toml_range(file, span.clone())),
+        )
+    })
+}
+
+/// Confirms that the user has not accidentally configured ordering requirements for groups that
+/// aren't configured.
+fn check_module_item_order_groupings(file: &SourceFile, conf: &mut TryConf) {
+    // Only check the file that configured either of the options, the values may have been inherited.
+    if !["module_item_order_groupings", "module_items_ordered_within_groupings"]
+        .iter()
+        .any(|name| conf.value_spans.contains_key(*name))
+    {
+        return;
+    }
+
+    if let SourceItemOrderingWithinModuleItemGroupings::Custom(groupings) =
+        &conf.conf.module_items_ordered_within_groupings
+    {
+        for grouping in groupings {
+            if !conf.conf.module_item_order_groupings.is_grouping(grouping) {
+                // Since this isn't fixable by rustfix, don't emit a `Suggestion`. This just adds some useful
+                // info for the user instead.
+
+                let names = conf.conf.module_item_order_groupings.grouping_names();
+                let suggestion = suggest_candidate(grouping, names.iter().map(String::as_str))
+                    .map(|s| format!(" perhaps you meant `{s}`?"))
+                    .unwrap_or_default();
+                let names = names.iter().map(|s| format!("`{s}`")).join(", ");
+                let message = format!(
+                    "unknown ordering group: `{grouping}` was not specified in `module-items-ordered-within-groupings`,{suggestion} expected one of: {names}"
+                );
+
+                let span = conf
+                    .value_spans
+                    .get("module_item_order_groupings")
+                    .cloned()
+                    .unwrap_or_default();
+                conf.errors.push(ConfError::spanned(file, message, None, span));
+            }
+        }
     }
 }
 
-fn extend_vec_if_indicator_present(vec: &mut Vec<String>, default: &[&str]) {
-    if vec.contains(&"..".to_string()) {
-        vec.extend(default.iter().map(ToString::to_string));
+/// If `vec` contains the `".."` indicator, appends the values of `base` that it doesn't already
+/// contain.
+fn extend_vec_if_indicator_present(vec: &mut Vec<String>, base: &[String]) {
+    if vec.iter().any(|value| value == "..") {
+        for value in base {
+            if !vec.contains(value) {
+                vec.push(value.clone());
+            }
+        }
     }
 }
 
+/// Moves the values of `base` in front of those of `vec`.
+fn prepend_base<T>(vec: &mut Vec<T>, base: &mut Vec<T>) {
+    vec.splice(0..0, base.drain(..));
+}
+
 impl Conf {
-    pub fn read(sess: &Session, path: &io::Result<(Option<PathBuf>, Vec<String>)>) -> &'static Conf {
+    /// Combines the options set in a configuration file with `base`, the configuration inherited
+    /// from a parent file or the default one.
+    ///
+    /// Options that weren't set take the value of `base`. Lists of disallowed paths accumulate, so
+    /// that a file cannot drop an item disallowed by its parent. Lists that support the `".."`
+    /// indicator are extended with the values of `base` when it's present, all other options
+    /// replace the inherited value.
+    fn merge(&mut self, mut base: Conf, value_spans: &HashMap<String, Range<usize>>) {
+        let is_set = |name: &str| value_spans.contains_key(name);
+
+        if is_set("await_holding_invalid_types") {
+            prepend_base(
+                &mut self.await_holding_invalid_types,
+                &mut base.await_holding_invalid_types,
+            );
+        }
+        if is_set("disallowed_trait_impls") {
+            prepend_base(&mut self.disallowed_trait_impls, &mut base.disallowed_trait_impls);
+        }
+        for (name, list, base_list) in [
+            (
+                "disallowed_fields",
+                &mut self.disallowed_fields,
+                &mut base.disallowed_fields,
+            ),
+            (
+                "disallowed_macros",
+                &mut self.disallowed_macros,
+                &mut base.disallowed_macros,
+            ),
+            (
+                "disallowed_methods",
+                &mut self.disallowed_methods,
+                &mut base.disallowed_methods,
+            ),
+            (
+                "disallowed_types",
+                &mut self.disallowed_types,
+                &mut base.disallowed_types,
+            ),
+        ] {
+            if is_set(name) {
+                prepend_base(list, base_list);
+            }
+        }
+
+        for (list, base_list) in [
+            (&mut self.allow_renamed_params_for, &base.allow_renamed_params_for),
+            (
+                &mut self.allowed_idents_below_min_chars,
+                &base.allowed_idents_below_min_chars,
+            ),
+            (&mut self.allowed_prefixes, &base.allowed_prefixes),
+            (&mut self.disallowed_names, &base.disallowed_names),
+            (&mut self.doc_valid_idents, &base.doc_valid_idents),
+        ] {
+            extend_vec_if_indicator_present(list, base_list);
+        }
+
+        if is_set("profiles") {
+            for (name, profile) in mem::take(&mut base.profiles) {
+                self.profiles.entry(name).or_insert(profile);
+            }
+        }
+
+        self.inherit_unset(base, value_spans);
+    }
+
+    /// Completes the options set in each `[[overrides]]` table with the ones of this configuration.
+    fn resolve_overrides(&mut self) {
+        let mut overrides = mem::take(&mut self.overrides);
+        for conf_override in &mut overrides {
+            conf_override.conf.merge(self.clone(), &conf_override.value_spans);
+        }
+        self.overrides = overrides;
+    }
+
+    /// Returns the `[[overrides]]` tables whose `paths` match the file containing `span`, in order.
+    fn overrides_for_span(&self, sess: &Session, span: Span) -> impl Iterator<Item = &ConfOverride> {
+        let path = if self.overrides.is_empty() {
+            None
+        } else {
+            let source_map = sess.source_map();
+            let file = source_map.lookup_source_file(span.source_callsite().lo());
+            if let FileName::Real(name) = &file.name
+                && let Some(path) = name.local_path()
+                && let Some(working_dir) = source_map.working_dir().local_path()
+            {
+                // `root` is canonical, e.g. when the working directory is behind a symlink like the
+                // `/tmp` of macOS
+                Some(canonical_path(&working_dir.join(path)))
+            } else {
+                None
+            }
+        };
+        path.into_iter().flat_map(move |path| {
+            self.overrides
+                .iter()
+                .filter(move |conf_override| conf_override.matches(&path))
+        })
+    }
+
+    /// Warns about the lints of the `allow` lists of `[[overrides]]` which aren't among `lints`,
+    /// given like `Lint::name_lower`.
+    pub fn check_override_lints(&self, sess: &Session, lints: &[String]) {
+        for conf_override in &self.overrides {
+            for lint in &conf_override.allow {
+                if lints.contains(lint) {
+                    continue;
+                }
+                let mut diag = sess.dcx().struct_span_warn(
+                    conf_override.allow_span,
+                    format!("error reading Clippy's configuration file: unknown lint `{lint}`"),
+                );
+                if let Some(suggestion) = suggest_candidate(lint, lints.iter().map(String::as_str)) {
+                    diag.help(format!("did you mean `{suggestion}`?"));
+                }
+                diag.emit();
+            }
+        }
+    }
+
+    /// Returns the configuration that applies to the file containing `span`: the one of the first
+    /// `[[overrides]]` table whose `paths` match the file, or `self` if there is none.
+    ///
+    /// Only the options in `OVERRIDABLE_OPTIONS` can differ from `self`, lints using one of them
+    /// should look it up here rather than read it once when the lint pass is created.
+    pub fn for_span(&self, sess: &Session, span: Span) -> &Self {
+        self.overrides_for_span(sess, span)
+            .next()
+            .map_or(self, |conf_override| &conf_override.conf)
+    }
+
+    /// Whether one of the `[[overrides]]` tables matching the file containing `span` allows `lint`,
+    /// given like `Lint::name_lower`. Checked by `clippy_utils` when a lint is emitted.
+    pub fn allows_lint(&self, sess: &Session, span: Span, lint: &str) -> bool {
+        self.overrides_for_span(sess, span)
+            .any(|conf_override| conf_override.allow.iter().any(|allowed| allowed == lint))
+    }
+
+    /// Renders the configuration as TOML, each option preceded by comments telling where its value
+    /// comes from and which lints it affects. Used by `cargo clippy --print-config`.
+    pub fn to_annotated_toml(&self, sess: &Session) -> String {
+        use fmt::Write;
+
+        let metadata = get_configuration_metadata();
+        let mut toml = String::new();
+        for (meta, (name, value)) in metadata.iter().zip(self.serialized_values()) {
+            if meta.deprecation_reason.is_some() {
+                continue;
+            }
+            let origin = match self.origins.get(name) {
+                Some(ValueOrigin::Conf(span)) => describe_span(sess, *span),
+                Some(ValueOrigin::RustVersion) => "`rust-version` in `Cargo.toml`".to_string(),
+                None => "default".to_string(),
+            };
+            writeln!(toml, "# {origin}").unwrap();
+            if !meta.lints.is_empty() {
+                writeln!(toml, "# lints: {}", meta.lints.join(", ")).unwrap();
+            }
+            match value {
+                Some(value) => writeln!(toml, "{} = {value}\n", meta.name).unwrap(),
+                // e.g. `None`, show the documented default instead
+                None => writeln!(toml, "# {} = {}\n", meta.name, meta.default).unwrap(),
+            }
+        }
+
+        for conf_override in &self.overrides {
+            writeln!(toml, "# declared in `{}`", conf_override.root.display()).unwrap();
+            writeln!(toml, "[[overrides]]").unwrap();
+            if let Some(paths) = serialize_value(&conf_override.paths) {
+                writeln!(toml, "paths = {paths}").unwrap();
+            }
+            for (name, value) in conf_override.conf.serialized_values() {
+                if let (Some(ValueOrigin::Conf(span)), Some(value)) = (conf_override.conf.origins.get(name), value) {
+                    writeln!(toml, "# {}", describe_span(sess, *span)).unwrap();
+                    writeln!(toml, "{} = {value}", name.replace('_', "-")).unwrap();
+                }
+            }
+            writeln!(toml).unwrap();
+        }
+
+        toml
+    }
+
+    pub fn read(sess: &Session, path: &io::Result<(Option<ConfLocation>, Vec<String>)>) -> &'static Conf {
         static CONF: OnceLock<Conf> = OnceLock::new();
         CONF.get_or_init(|| Conf::read_inner(sess, path))
     }
 
-    fn read_inner(sess: &Session, path: &io::Result<(Option<PathBuf>, Vec<String>)>) -> Conf {
+    fn read_inner(sess: &Session, path: &io::Result<(Option<ConfLocation>, Vec<String>)>) -> Conf {
         match path {
             Ok((_, warnings)) => {
                 for warning in warnings {
@@ -1086,20 +2048,24 @@ impl Conf {
         let TryConf {
             mut conf,
             value_spans: _,
+            parent: _,
+            paths: _,
+            allow: _,
+            allow_span: _,
+            overrides: _,
             errors,
             warnings,
         } = match path {
-            Ok((Some(path), _)) => match sess.source_map().load_file(path) {
-                Ok(file) => deserialize(&file),
-                Err(error) => {
-                    sess.dcx().err(format!("failed to read `{}`: {error}", path.display()));
-                    TryConf::default()
-                },
-            },
+            Ok((Some(location), _)) => read_conf_file(sess.source_map(), Some(sess.dcx()), location, &mut Vec::new()),
             _ => TryConf::default(),
         };
 
+        let msrv_set = conf.origins.contains_key("msrv");
         conf.msrv.read_cargo(sess);
+        if !msrv_set && env::var_os("CARGO_PKG_RUST_VERSION").is_some() {
+            conf.origins.insert("msrv".to_string(), ValueOrigin::RustVersion);
+        }
+        conf.resolve_overrides();
 
         // all conf errors are non-fatal, we just use the default conf in case of error
         for error in errors {
@@ -1126,6 +2092,31 @@ impl Conf {
     }
 }
 
+/// Canonicalizes `path` once, as the configuration of a file is looked up for each of its lints.
+fn canonical_path(path: &Path) -> PathBuf {
+    static CANONICAL_PATHS: LazyLock<Mutex<HashMap<PathBuf, PathBuf>>> = LazyLock::new(Mutex::default);
+
+    let mut paths = CANONICAL_PATHS.lock().unwrap();
+    if let Some(canonical) = paths.get(path) {
+        return canonical.clone();
+    }
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    paths.insert(path.to_path_buf(), canonical.clone());
+    canonical
+}
+
+/// Formats the location of `span` as `path:line`.
+fn describe_span(sess: &Session, span: Span) -> String {
+    let loc = sess.source_map().lookup_char_pos(span.lo());
+    if let FileName::Real(name) = &loc.file.name
+        && let Some(path) = name.local_path()
+    {
+        format!("`{}:{}`", path.display(), loc.line)
+    } else {
+        format!("`{:?}:{}`", loc.file.name, loc.line)
+    }
+}
+
 const SEPARATOR_WIDTH: usize = 4;
 
 #[derive(Debug)]
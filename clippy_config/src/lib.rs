@@ -15,9 +15,16 @@ extern crate rustc_middle;
 extern crate rustc_session;
 extern crate rustc_span;
 
+pub mod baseline;
+pub mod clippy_args;
 mod conf;
 mod metadata;
+mod schema;
 pub mod types;
 
-pub use conf::{Conf, get_configuration_metadata, lookup_conf_file, sanitize_explanation};
+pub use conf::{
+    Conf, ConfLocation, get_configuration_metadata, lookup_conf_file, lookup_conf_file_in, read_lint_profile,
+    sanitize_explanation,
+};
 pub use metadata::ClippyConfiguration;
+pub use schema::configuration_json_schema;
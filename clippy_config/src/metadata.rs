@@ -1,4 +1,5 @@
 use itertools::Itertools;
+use serde_json::Value;
 use std::fmt;
 
 #[derive(Debug, Clone, Default)]
@@ -8,6 +9,8 @@ pub struct ClippyConfiguration {
     pub lints: &'static [&'static str],
     pub doc: &'static str,
     pub deprecation_reason: Option<&'static str>,
+    /// The JSON Schema of the option's values, along with its default value.
+    pub schema: Value,
 }
 
 impl fmt::Display for ClippyConfiguration {
@@ -37,4 +40,18 @@ impl ClippyConfiguration {
         const BOOK_CONFIGS_PATH: &str = "https://doc.rust-lang.org/clippy/lint_configuration.html";
         format!("[`{}`]: {BOOK_CONFIGS_PATH}#{}", self.name, self.name)
     }
+
+    pub fn to_json_schema(&self) -> Value {
+        let mut schema = self.schema.clone();
+        schema["description"] = self
+            .doc
+            .lines()
+            .map(|x| x.strip_prefix(' ').unwrap_or(x))
+            .join("\n")
+            .into();
+        if self.deprecation_reason.is_some() {
+            schema["deprecated"] = true.into();
+        }
+        schema
+    }
 }
@@ -0,0 +1,305 @@
+use crate::conf::OVERRIDABLE_OPTIONS;
+use crate::get_configuration_metadata;
+use crate::types::{
+    DisallowedPath, InherentImplLintScope, LintProfile, MacroMatcher, MatchLintBehaviour, PubUnderscoreFieldsBehaviour,
+    Rename, SourceItemOrdering, SourceItemOrderingModuleItemGroupings, SourceItemOrderingModuleItemKind,
+    SourceItemOrderingTraitAssocItemKind, SourceItemOrderingTraitAssocItemKinds,
+    SourceItemOrderingWithinModuleItemGroupings,
+};
+use clippy_utils::msrvs::Msrv;
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+
+/// The JSON Schema of the values accepted by a configuration option of this type.
+pub(crate) trait ConfSchema {
+    fn schema() -> Value;
+}
+
+/// Returns the schema of an option of type `T`, with its default value if it can be written in
+/// the configuration.
+pub(crate) fn option_schema<T: ConfSchema>(default: Option<Value>) -> Value {
+    let mut schema = T::schema();
+    if let Some(default) = default.filter(|default| !default.is_null()) {
+        schema["default"] = default;
+    }
+    schema
+}
+
+fn enum_schema<T: Serialize>(variants: &[T]) -> Value {
+    json!({ "enum": variants })
+}
+
+impl ConfSchema for bool {
+    fn schema() -> Value {
+        json!({ "type": "boolean" })
+    }
+}
+
+impl ConfSchema for u64 {
+    fn schema() -> Value {
+        json!({ "type": "integer", "minimum": 0 })
+    }
+}
+
+impl ConfSchema for usize {
+    fn schema() -> Value {
+        json!({ "type": "integer", "minimum": 0 })
+    }
+}
+
+impl ConfSchema for String {
+    fn schema() -> Value {
+        json!({ "type": "string" })
+    }
+}
+
+// `None` can't be written in TOML, the option is just left unset.
+impl<T: ConfSchema> ConfSchema for Option<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+}
+
+impl<T: ConfSchema> ConfSchema for Vec<T> {
+    fn schema() -> Value {
+        json!({ "type": "array", "items": T::schema() })
+    }
+}
+
+impl<T: ConfSchema> ConfSchema for BTreeMap<String, T> {
+    fn schema() -> Value {
+        json!({ "type": "object", "additionalProperties": T::schema() })
+    }
+}
+
+impl<T: ConfSchema, U: ConfSchema> ConfSchema for (T, U) {
+    fn schema() -> Value {
+        json!({
+            "type": "array",
+            "items": [T::schema(), U::schema()],
+            "minItems": 2,
+            "maxItems": 2,
+        })
+    }
+}
+
+impl ConfSchema for Msrv {
+    fn schema() -> Value {
+        json!({ "type": "string", "pattern": r"^[0-9]+\.[0-9]+(\.[0-9]+)?$" })
+    }
+}
+
+impl ConfSchema for Rename {
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": String::schema(),
+                "rename": String::schema(),
+            },
+            "required": ["path", "rename"],
+            "additionalProperties": false,
+        })
+    }
+}
+
+impl ConfSchema for LintProfile {
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "allow": Vec::<String>::schema(),
+                "warn": Vec::<String>::schema(),
+                "deny": Vec::<String>::schema(),
+                "forbid": Vec::<String>::schema(),
+            },
+            "additionalProperties": false,
+        })
+    }
+}
+
+impl<const REPLACEMENT_ALLOWED: bool> ConfSchema for DisallowedPath<REPLACEMENT_ALLOWED> {
+    fn schema() -> Value {
+        let mut properties = Map::new();
+        properties.insert("path".into(), String::schema());
+        properties.insert("reason".into(), String::schema());
+        if REPLACEMENT_ALLOWED {
+            properties.insert("replacement".into(), String::schema());
+        }
+        properties.insert("allow-invalid".into(), bool::schema());
+        properties.insert("allowed-in".into(), Vec::<String>::schema());
+        properties.insert("for-types".into(), Vec::<String>::schema());
+        json!({
+            "anyOf": [
+                String::schema(),
+                {
+                    "type": "object",
+                    "properties": properties,
+                    "required": ["path"],
+                    "additionalProperties": false,
+                },
+            ],
+        })
+    }
+}
+
+impl ConfSchema for MacroMatcher {
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": String::schema(),
+                "brace": enum_schema(&["(", "{", "["]),
+            },
+            "required": ["name", "brace"],
+            "additionalProperties": false,
+        })
+    }
+}
+
+impl ConfSchema for SourceItemOrdering {
+    fn schema() -> Value {
+        json!({
+            "type": "array",
+            "items": enum_schema(&["enum", "impl", "module", "struct", "trait"]),
+            "uniqueItems": true,
+        })
+    }
+}
+
+impl ConfSchema for SourceItemOrderingModuleItemGroupings {
+    fn schema() -> Value {
+        json!({
+            "type": "array",
+            "items": {
+                "type": "array",
+                "items": [
+                    String::schema(),
+                    {
+                        "type": "array",
+                        "items": enum_schema(&SourceItemOrderingModuleItemKind::all_variants()),
+                    },
+                ],
+                "minItems": 2,
+                "maxItems": 2,
+            },
+        })
+    }
+}
+
+impl ConfSchema for SourceItemOrderingTraitAssocItemKinds {
+    fn schema() -> Value {
+        let variants = SourceItemOrderingTraitAssocItemKind::all_variants();
+        json!({
+            "type": "array",
+            "items": enum_schema(&variants),
+            "minItems": variants.len(),
+            "maxItems": variants.len(),
+            "uniqueItems": true,
+        })
+    }
+}
+
+impl ConfSchema for SourceItemOrderingWithinModuleItemGroupings {
+    fn schema() -> Value {
+        json!({ "anyOf": [enum_schema(&["all", "none"]), Vec::<String>::schema()] })
+    }
+}
+
+impl ConfSchema for MatchLintBehaviour {
+    fn schema() -> Value {
+        enum_schema(&[Self::AllTypes, Self::WellKnownTypes, Self::Never])
+    }
+}
+
+impl ConfSchema for PubUnderscoreFieldsBehaviour {
+    fn schema() -> Value {
+        enum_schema(&[Self::PubliclyExported, Self::AllPubFields])
+    }
+}
+
+impl ConfSchema for InherentImplLintScope {
+    fn schema() -> Value {
+        enum_schema(&[Self::Crate, Self::File, Self::Module])
+    }
+}
+
+/// Returns the JSON Schema of the configuration files, which editors can use to complete and
+/// validate them.
+pub fn configuration_json_schema() -> Value {
+    let metadata = get_configuration_metadata();
+
+    let mut properties: Map<String, Value> = metadata
+        .iter()
+        .map(|conf| (conf.name.clone(), conf.to_json_schema()))
+        .collect();
+    let mut override_properties: Map<String, Value> = metadata
+        .iter()
+        .filter(|conf| OVERRIDABLE_OPTIONS.contains(&conf.name.replace('-', "_").as_str()))
+        .map(|conf| (conf.name.clone(), conf.to_json_schema()))
+        .collect();
+    override_properties.insert(
+        "paths".into(),
+        json!({
+            "description": "Glob patterns of the files the override applies to, relative to the directory \
+                of this configuration.",
+            "type": "array",
+            "items": String::schema(),
+        }),
+    );
+    override_properties.insert(
+        "allow".into(),
+        json!({
+            "description": "Clippy lints allowed in the files the override applies to, like `clippy::unwrap_used`.",
+            "type": "array",
+            "items": String::schema(),
+        }),
+    );
+
+    properties.insert(
+        "inherit".into(),
+        json!({
+            "description": "Use the next configuration found in the parent directories for the options that \
+                aren't set here.",
+            "type": "boolean",
+        }),
+    );
+    properties.insert(
+        "extends".into(),
+        json!({
+            "description": "Path of a configuration to use for the options that aren't set here, relative to \
+                the directory of this configuration.",
+            "type": "string",
+        }),
+    );
+    properties.insert(
+        "overrides".into(),
+        json!({
+            "description": "Values of some options for the files matching some paths.",
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": override_properties,
+                "required": ["paths"],
+                "additionalProperties": false,
+            },
+        }),
+    );
+    properties.insert(
+        "third-party".into(),
+        json!({
+            "description": "Ignored by Clippy.",
+            "type": "object",
+        }),
+    );
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "Clippy configuration",
+        "description": "The configuration of Clippy, in `clippy.toml` or `.clippy.toml`.",
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    })
+}
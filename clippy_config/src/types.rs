@@ -1,26 +1,85 @@
-use clippy_utils::paths::{PathNS, find_crates, lookup_path};
-use rustc_data_structures::fx::FxHashMap;
+use crate::conf::component_matches;
+use clippy_utils::paths::{PathNS, find_crates, lookup_path, lookup_path_matching, lookup_path_str};
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
+use rustc_data_structures::unord::{UnordItems, UnordSet};
 use rustc_errors::{Applicability, Diag};
-use rustc_hir::PrimTy;
 use rustc_hir::def::DefKind;
-use rustc_hir::def_id::DefIdMap;
-use rustc_middle::ty::TyCtxt;
+use rustc_hir::def_id::{DefId, DefIdMap};
+use rustc_hir::{HirId, PrimTy};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{GenericArgsRef, Ty, TyCtxt};
 use rustc_span::{Span, Symbol};
 use serde::de::{self, Deserializer, Visitor};
 use serde::{Deserialize, Serialize, ser};
 use std::collections::HashMap;
 use std::fmt;
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Rename {
     pub path: String,
     pub rename: String,
 }
 
+/// A named set of lint levels from the `profiles` option, selected with
+/// `cargo clippy --lint-profile <NAME>`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintProfile {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warn: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbid: Vec<String>,
+}
+
+/// The lint groups of Clippy, without the `clippy::` prefix.
+pub(crate) const CLIPPY_LINT_GROUPS: &[&str] = &[
+    "all",
+    "cargo",
+    "complexity",
+    "correctness",
+    "nursery",
+    "pedantic",
+    "perf",
+    "restriction",
+    "style",
+    "suspicious",
+];
+
+impl LintProfile {
+    /// Returns the lint level flags to pass to the compiler, e.g. `-Wclippy::missing_errors_doc`.
+    ///
+    /// The lint groups come first and the single lints last, each ordered from `allow` to `forbid`.
+    /// As the later flags take precedence, `warn = ["clippy::pedantic"]` along with
+    /// `allow = ["clippy::module_name_repetitions"]` allows the latter lint.
+    pub fn lint_args(&self) -> Vec<String> {
+        let is_group = |lint: &str| {
+            lint == "warnings"
+                || lint
+                    .strip_prefix("clippy::")
+                    .is_some_and(|name| CLIPPY_LINT_GROUPS.contains(&name))
+        };
+        let levels = [
+            ("-A", &self.allow),
+            ("-W", &self.warn),
+            ("-D", &self.deny),
+            ("-F", &self.forbid),
+        ];
+        let (groups, lints): (Vec<_>, Vec<_>) = levels
+            .iter()
+            .flat_map(|(flag, lints)| lints.iter().map(move |lint| format!("{flag}{lint}")))
+            .partition(|arg| is_group(&arg[2..]));
+        groups.into_iter().chain(lints).collect()
+    }
+}
+
 pub type DisallowedPathWithoutReplacement = DisallowedPath<false>;
 
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct DisallowedPath<const REPLACEMENT_ALLOWED: bool = true> {
     path: String,
     reason: Option<String>,
@@ -31,6 +90,15 @@ pub struct DisallowedPath<const REPLACEMENT_ALLOWED: bool = true> {
     /// This could be useful when conditional compilation is used, or when a clippy.toml file is
     /// shared among multiple projects.
     allow_invalid: bool,
+    /// The modules where the item can be used anyway, as paths starting with a crate name or
+    /// `crate`. A path allows the module it refers to and the modules it contains.
+    #[serde(rename = "allowed-in", skip_serializing_if = "Vec::is_empty")]
+    allowed_in: Vec<String>,
+    /// The types whose implementations of the trait are disallowed, for `disallowed-trait-impls`,
+    /// or the types the method is disallowed with, for `disallowed-methods`. It applies to every
+    /// type when it's empty.
+    #[serde(rename = "for-types", skip_serializing_if = "Vec::is_empty")]
+    for_types: Vec<String>,
     /// The span of the `DisallowedPath`.
     ///
     /// Used for diagnostics.
@@ -52,11 +120,36 @@ impl<'de, const REPLACEMENT_ALLOWED: bool> Deserialize<'de> for DisallowedPath<R
             reason: enum_.reason().map(ToOwned::to_owned),
             replacement: enum_.replacement().map(ToOwned::to_owned),
             allow_invalid: enum_.allow_invalid(),
+            allowed_in: enum_.allowed_in().to_vec(),
+            for_types: enum_.for_types().to_vec(),
             span: Span::default(),
         })
     }
 }
 
+/// Whether the path of `def_id` matches `pattern`, a path starting with a crate name or `crate`
+/// whose segments can contain the `*` and `?` wildcards. With `prefix`, the items nested in an
+/// item matching `pattern` match too.
+fn def_path_matches(tcx: TyCtxt<'_>, def_id: DefId, pattern: &str, prefix: bool) -> bool {
+    let mut pattern = pattern.split("::");
+    let pattern_crate = pattern.next().unwrap_or_default();
+    let pattern: Vec<&str> = pattern.collect();
+    let path: Vec<Symbol> = tcx
+        .def_path(def_id)
+        .data
+        .iter()
+        .filter_map(|data| data.data.get_opt_name())
+        .collect();
+
+    ((def_id.is_local() && pattern_crate == "crate")
+        || component_matches(pattern_crate, tcx.crate_name(def_id.krate).as_str()))
+        && (pattern.len() == path.len() || prefix && pattern.len() < path.len())
+        && pattern
+            .iter()
+            .zip(&path)
+            .all(|(pattern, name)| component_matches(pattern, name.as_str()))
+}
+
 // `DisallowedPathEnum` is an implementation detail to enable the `Deserialize` implementation just
 // above. `DisallowedPathEnum` is not meant to be used outside of this file.
 #[derive(Debug, Deserialize, Serialize)]
@@ -69,6 +162,10 @@ enum DisallowedPathEnum {
         replacement: Option<String>,
         #[serde(rename = "allow-invalid")]
         allow_invalid: Option<bool>,
+        #[serde(rename = "allowed-in")]
+        allowed_in: Option<Vec<String>>,
+        #[serde(rename = "for-types")]
+        for_types: Option<Vec<String>>,
     },
 }
 
@@ -77,8 +174,16 @@ impl<const REPLACEMENT_ALLOWED: bool> DisallowedPath<REPLACEMENT_ALLOWED> {
         &self.path
     }
 
+    /// Whether the path is a pattern like `std::env::set_*`, which can match several items.
+    pub fn is_pattern(&self) -> bool {
+        self.path.contains(['*', '?'])
+    }
+
     pub fn diag_amendment(&self, span: Span) -> impl FnOnce(&mut Diag<'_, ()>) {
         move |diag| {
+            if self.is_pattern() {
+                diag.note(format!("disallowed by the pattern `{}`", self.path));
+            }
             if let Some(replacement) = &self.replacement {
                 diag.span_suggestion(
                     span,
@@ -96,6 +201,52 @@ impl<const REPLACEMENT_ALLOWED: bool> DisallowedPath<REPLACEMENT_ALLOWED> {
         self.span
     }
 
+    /// Whether the item can be used at `hir_id` because its module is listed in `allowed-in`.
+    pub fn is_allowed_at(&self, tcx: TyCtxt<'_>, hir_id: HirId) -> bool {
+        if self.allowed_in.is_empty() {
+            return false;
+        }
+
+        let module = tcx.parent_module(hir_id).to_def_id();
+        self.allowed_in.iter().any(|allowed| {
+            // `crate::cli::*` means `crate::cli` and the modules it contains, like `crate::cli`
+            let allowed = allowed.strip_suffix("::*").unwrap_or(allowed);
+            def_path_matches(tcx, module, allowed, true)
+        })
+    }
+
+    /// Whether the entry applies to an implementation for `self_ty`, i.e. whether `for-types` is
+    /// empty or matches it. The paths without wildcards are resolved, so that they can name a type
+    /// through a re-export like `std::collections::HashSet`.
+    pub fn applies_to_impl_for(&self, tcx: TyCtxt<'_>, self_ty: Ty<'_>) -> bool {
+        self.for_types.is_empty()
+            || self_ty.ty_adt_def().is_some_and(|adt| {
+                self.for_types.iter().any(|for_type| {
+                    def_path_matches(tcx, adt.did(), for_type, false)
+                        || !for_type.contains(['*', '?'])
+                            && lookup_path_str(tcx, PathNS::Type, for_type).contains(&adt.did())
+                })
+            })
+    }
+
+    /// Whether the entry applies to a use of the method with the generic arguments `args`, i.e.
+    /// whether `for-types` is empty or matches one of their types. They include `Self` for the
+    /// method of a trait, and the type a method like `Iterator::collect` returns.
+    pub fn applies_to_args(&self, tcx: TyCtxt<'_>, args: GenericArgsRef<'_>) -> bool {
+        self.for_types.is_empty() || args.types().any(|ty| self.applies_to_impl_for(tcx, ty))
+    }
+
+    /// Whether `for-types` is set, which is only supported by `disallowed-methods` and
+    /// `disallowed-trait-impls`.
+    pub fn has_for_types(&self) -> bool {
+        !self.for_types.is_empty()
+    }
+
+    /// Whether `allowed-in` is set, which is only supported by the `disallowed-*` options.
+    pub fn has_allowed_in(&self) -> bool {
+        !self.allowed_in.is_empty()
+    }
+
     pub fn set_span(&mut self, span: Span) {
         self.span = span;
     }
@@ -128,9 +279,30 @@ impl DisallowedPathEnum {
             Self::Simple(_) => false,
         }
     }
+
+    fn allowed_in(&self) -> &[String] {
+        match &self {
+            Self::WithReason {
+                allowed_in: Some(allowed_in),
+                ..
+            } => allowed_in,
+            _ => &[],
+        }
+    }
+
+    fn for_types(&self) -> &[String] {
+        match &self {
+            Self::WithReason {
+                for_types: Some(for_types),
+                ..
+            } => for_types,
+            _ => &[],
+        }
+    }
 }
 
-/// Creates a map of disallowed items to the reason they were disallowed.
+/// Creates a map of disallowed items to the reason they were disallowed, along with the path of
+/// the item. An item matched by a pattern like `std::env::set_*` is mapped to its own path.
 #[expect(clippy::type_complexity)]
 pub fn create_disallowed_map<const REPLACEMENT_ALLOWED: bool>(
     tcx: TyCtxt<'_>,
@@ -140,14 +312,25 @@ pub fn create_disallowed_map<const REPLACEMENT_ALLOWED: bool>(
     predicate_description: &str,
     allow_prim_tys: bool,
 ) -> (
-    DefIdMap<(&'static str, &'static DisallowedPath<REPLACEMENT_ALLOWED>)>,
-    FxHashMap<PrimTy, (&'static str, &'static DisallowedPath<REPLACEMENT_ALLOWED>)>,
+    DefIdMap<(Symbol, &'static DisallowedPath<REPLACEMENT_ALLOWED>)>,
+    FxHashMap<PrimTy, (Symbol, &'static DisallowedPath<REPLACEMENT_ALLOWED>)>,
 ) {
-    let mut def_ids: DefIdMap<(&'static str, &'static DisallowedPath<REPLACEMENT_ALLOWED>)> = DefIdMap::default();
-    let mut prim_tys: FxHashMap<PrimTy, (&'static str, &'static DisallowedPath<REPLACEMENT_ALLOWED>)> =
-        FxHashMap::default();
+    let mut def_ids: DefIdMap<(Symbol, &'static DisallowedPath<REPLACEMENT_ALLOWED>)> = DefIdMap::default();
+    let mut prim_tys: FxHashMap<PrimTy, (Symbol, &'static DisallowedPath<REPLACEMENT_ALLOWED>)> = FxHashMap::default();
     for disallowed_path in disallowed_paths {
         let path = disallowed_path.path();
+        if disallowed_path.is_pattern() {
+            insert_pattern_matches(
+                tcx,
+                disallowed_path,
+                ns,
+                &def_kind_predicate,
+                predicate_description,
+                &mut def_ids,
+            );
+            continue;
+        }
+
         let sym_path: Vec<Symbol> = path.split("::").map(Symbol::intern).collect();
         let mut resolutions = lookup_path(tcx, ns, &sym_path);
         resolutions.retain(|&def_id| def_kind_predicate(tcx.def_kind(def_id)));
@@ -177,13 +360,10 @@ pub fn create_disallowed_map<const REPLACEMENT_ALLOWED: bool>(
             } else {
                 format!("`{path}` does not refer to a reachable {predicate_description}")
             };
-            tcx.sess
-                .dcx()
-                .struct_span_warn(disallowed_path.span(), message)
-                .with_help("add `allow-invalid = true` to the entry to suppress this warning")
-                .emit();
+            emit_invalid_path_warning(tcx, disallowed_path, message);
         }
 
+        let path = Symbol::intern(path);
         for def_id in resolutions {
             def_ids.insert(def_id, (path, disallowed_path));
         }
@@ -195,6 +375,117 @@ pub fn create_disallowed_map<const REPLACEMENT_ALLOWED: bool>(
     (def_ids, prim_tys)
 }
 
+/// Inserts the items matched by a pattern like `std::env::set_*` in `def_ids`. Only the last
+/// segment of the pattern can contain wildcards.
+fn insert_pattern_matches<const REPLACEMENT_ALLOWED: bool>(
+    tcx: TyCtxt<'_>,
+    disallowed_path: &'static DisallowedPath<REPLACEMENT_ALLOWED>,
+    ns: PathNS,
+    def_kind_predicate: impl Fn(DefKind) -> bool,
+    predicate_description: &str,
+    def_ids: &mut DefIdMap<(Symbol, &'static DisallowedPath<REPLACEMENT_ALLOWED>)>,
+) {
+    let path = disallowed_path.path();
+    let Some((parent, last)) = path.rsplit_once("::") else {
+        emit_invalid_path_warning(
+            tcx,
+            disallowed_path,
+            format!("`{path}` matches whole crates, the pattern needs at least two segments"),
+        );
+        return;
+    };
+    if parent.contains(['*', '?']) {
+        emit_invalid_path_warning(
+            tcx,
+            disallowed_path,
+            format!("`{path}` has wildcards before its last segment, which isn't supported"),
+        );
+        return;
+    }
+
+    let parent: Vec<Symbol> = parent.split("::").map(Symbol::intern).collect();
+    let mut resolutions = lookup_path_matching(tcx, ns, &parent, |name| component_matches(last, name.as_str()));
+    resolutions.retain(|&def_id| def_kind_predicate(tcx.def_kind(def_id)));
+
+    if resolutions.is_empty() && !disallowed_path.allow_invalid && !find_crates(tcx, parent[0]).is_empty() {
+        emit_invalid_path_warning(
+            tcx,
+            disallowed_path,
+            format!("`{path}` does not match any reachable {predicate_description}"),
+        );
+    }
+
+    for def_id in resolutions {
+        let item_path = Symbol::intern(&with_no_trimmed_paths!(tcx.def_path_str(def_id)));
+        def_ids.insert(def_id, (item_path, disallowed_path));
+    }
+}
+
+fn emit_invalid_path_warning<const REPLACEMENT_ALLOWED: bool>(
+    tcx: TyCtxt<'_>,
+    disallowed_path: &DisallowedPath<REPLACEMENT_ALLOWED>,
+    message: String,
+) {
+    tcx.sess
+        .dcx()
+        .struct_span_warn(disallowed_path.span(), message)
+        .with_help("add `allow-invalid = true` to the entry to suppress this warning")
+        .emit();
+}
+
+/// Keeps track of the disallowed paths that were never used in the crate, to report them when
+/// `warn-unused-disallowed-paths` is enabled.
+pub struct UnusedDisallowedPaths<const REPLACEMENT_ALLOWED: bool = true> {
+    /// The entries that refer to an item but weren't used yet, in the order of the configuration.
+    unused: FxIndexMap<Span, &'static DisallowedPath<REPLACEMENT_ALLOWED>>,
+}
+
+impl<const REPLACEMENT_ALLOWED: bool> UnusedDisallowedPaths<REPLACEMENT_ALLOWED> {
+    /// Tracks the entries of `disallowed_paths` that resolved to an item, i.e. the ones found in
+    /// `resolved`, or none of them if `enabled` is false.
+    pub fn new<'a>(
+        enabled: bool,
+        disallowed_paths: &'static [DisallowedPath<REPLACEMENT_ALLOWED>],
+        resolved: UnordItems<
+            &'a (Symbol, &'static DisallowedPath<REPLACEMENT_ALLOWED>),
+            impl Iterator<Item = &'a (Symbol, &'static DisallowedPath<REPLACEMENT_ALLOWED>)>,
+        >,
+    ) -> Self {
+        let mut unused = FxIndexMap::default();
+        if enabled {
+            let resolved: UnordSet<Span> = resolved.map(|(_, path)| path.span()).collect();
+            unused.extend(
+                disallowed_paths
+                    .iter()
+                    .filter(|path| resolved.contains(&path.span()))
+                    .map(|path| (path.span(), path)),
+            );
+        }
+        Self { unused }
+    }
+
+    pub fn mark_used(&mut self, disallowed_path: &DisallowedPath<REPLACEMENT_ALLOWED>) {
+        self.unused.shift_remove(&disallowed_path.span());
+    }
+
+    /// Warns about the entries that were never used.
+    pub fn emit(&self, tcx: TyCtxt<'_>, predicate_description: &str) {
+        for disallowed_path in self.unused.values() {
+            tcx.sess
+                .dcx()
+                .struct_span_warn(
+                    disallowed_path.span(),
+                    format!(
+                        "the disallowed {predicate_description} `{}` is never used in this crate",
+                        disallowed_path.path()
+                    ),
+                )
+                .with_help("remove the entry if the crates using the configuration don't need it anymore")
+                .emit();
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum MatchLintBehaviour {
     AllTypes,
@@ -202,7 +493,7 @@ pub enum MatchLintBehaviour {
     Never,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct MacroMatcher {
     pub name: String,
     pub braces: (char, char),
@@ -267,6 +558,20 @@ impl<'de> Deserialize<'de> for MacroMatcher {
     }
 }
 
+impl Serialize for MacroMatcher {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeStruct;
+
+        let mut matcher = serializer.serialize_struct("MacroMatcher", 2)?;
+        matcher.serialize_field("name", &self.name)?;
+        matcher.serialize_field("brace", &self.braces.0)?;
+        matcher.end()
+    }
+}
+
 /// Represents the item categories that can be ordered by the source ordering lint.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -282,6 +587,7 @@ pub enum SourceItemOrderingCategory {
 ///
 /// The [`Deserialize`] implementation checks that there are no duplicates in
 /// the user configuration.
+#[derive(Clone)]
 pub struct SourceItemOrdering(Vec<SourceItemOrderingCategory>);
 
 impl SourceItemOrdering {
@@ -671,28 +977,6 @@ impl Serialize for SourceItemOrderingWithinModuleItemGroupings {
     }
 }
 
-// these impls are never actually called but are used by the various config options that default to
-// empty lists
-macro_rules! unimplemented_serialize {
-    ($($t:ty,)*) => {
-        $(
-            impl Serialize for $t {
-                fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
-                where
-                    S: ser::Serializer,
-                {
-                    Err(ser::Error::custom("unimplemented"))
-                }
-            }
-        )*
-    }
-}
-
-unimplemented_serialize! {
-    Rename,
-    MacroMatcher,
-}
-
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum PubUnderscoreFieldsBehaviour {
     PubliclyExported,
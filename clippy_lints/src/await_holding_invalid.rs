@@ -9,7 +9,7 @@ use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::mir::CoroutineLayout;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::impl_lint_pass;
-use rustc_span::Span;
+use rustc_span::{Span, Symbol};
 
 declare_clippy_lint! {
     /// ### What it does
@@ -179,7 +179,7 @@ impl_lint_pass!(AwaitHolding => [
 ]);
 
 pub struct AwaitHolding {
-    def_ids: DefIdMap<(&'static str, &'static DisallowedPathWithoutReplacement)>,
+    def_ids: DefIdMap<(Symbol, &'static DisallowedPathWithoutReplacement)>,
 }
 
 impl AwaitHolding {
@@ -268,7 +268,7 @@ impl AwaitHolding {
 fn emit_invalid_type(
     cx: &LateContext<'_>,
     span: Span,
-    path: &'static str,
+    path: Symbol,
     disallowed_path: &'static DisallowedPathWithoutReplacement,
 ) {
     span_lint_and_then(
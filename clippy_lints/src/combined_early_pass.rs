@@ -14,8 +14,29 @@
 //! late passes via `lints_that_dont_need_to_run`, but the early pass runner has
 //! no such filtering, so a plain forward is equivalent and loses nothing.
 //!
+//! The fields are still wrapped in [`Timed`] to record their time when
+//! profiling, with the same per-method branch as the late version.
+//!
 //! [`combined_late_pass`]: crate::combined_late_pass
 
+use std::time::Duration;
+
+/// A pass paired with the time spent in it, only measured when profiling.
+pub struct Timed<P> {
+    pub(crate) pass: P,
+    pub(crate) time: Duration,
+}
+
+impl<P> Timed<P> {
+    #[inline]
+    pub fn new(pass: P) -> Self {
+        Timed {
+            pass,
+            time: Duration::ZERO,
+        }
+    }
+}
+
 /// Run one field's `check_*`.
 ///
 /// Fully qualified through [`rustc_lint::EarlyLintPass`] since some passes impl
@@ -24,7 +45,18 @@
 #[macro_export]
 macro_rules! run_combined_early_lint_pass_field {
     ($self:ident, $field:ident, $name:ident, ($($arg:expr),* $(,)?)) => {
-        rustc_lint::EarlyLintPass::$name(&mut $self.$field, $($arg),*);
+        rustc_lint::EarlyLintPass::$name(&mut $self.$field.pass, $($arg),*);
+    };
+}
+
+/// Like [`run_combined_early_lint_pass_field`], but adds the time of the call to
+/// the field's.
+#[macro_export]
+macro_rules! run_timed_combined_early_lint_pass_field {
+    ($self:ident, $field:ident, $name:ident, ($($arg:expr),* $(,)?)) => {
+        let start = std::time::Instant::now();
+        rustc_lint::EarlyLintPass::$name(&mut $self.$field.pass, $($arg),*);
+        $self.$field.time += start.elapsed();
     };
 }
 
@@ -32,7 +64,11 @@ macro_rules! run_combined_early_lint_pass_field {
 #[macro_export]
 macro_rules! expand_combined_early_lint_pass_method {
     ([$($field:ident),*], $self:ident, $name:ident, $args:tt) => ({
-        $($crate::run_combined_early_lint_pass_field!($self, $field, $name, $args);)*
+        if $self.profile.is_some() {
+            $($crate::run_timed_combined_early_lint_pass_field!($self, $field, $name, $args);)*
+        } else {
+            $($crate::run_combined_early_lint_pass_field!($self, $field, $name, $args);)*
+        }
     })
 }
 
@@ -47,12 +83,16 @@ macro_rules! expand_combined_early_lint_pass_methods {
     )
 }
 
-/// Declare the combined struct (one field per pass) plus its
+/// Declare the combined struct (one [`Timed`] field per pass) plus its
 /// `LintPass`/`EarlyLintPass` impls. The method list comes from
 /// `rustc_lint::early_lint_methods!` so it can't drift from rustc's.
 ///
 /// Each entry is `Field: Type = constructor`; `new`'s params (`conf`, ...) come
-/// from the caller so ctor exprs can name them without hygiene trouble.
+/// from the caller so ctor exprs can name them without hygiene trouble. When a
+/// [`PassProfile`] is given, the field times are recorded in it when the
+/// combined pass is dropped.
+///
+/// [`PassProfile`]: crate::pass_profile::PassProfile
 #[macro_export]
 macro_rules! combined_early_lint_pass {
     (
@@ -61,13 +101,23 @@ macro_rules! combined_early_lint_pass {
     ) => {
         #[allow(non_snake_case)]
         pub struct $name {
-            $($field: $fty,)*
+            $($field: $crate::combined_early_pass::Timed<$fty>,)*
+            profile: Option<$crate::pass_profile::PassProfile>,
         }
 
         impl $name {
-            pub fn new($($pname: $pty,)*) -> Self {
+            pub fn new($($pname: $pty,)* profile: Option<$crate::pass_profile::PassProfile>) -> Self {
                 Self {
-                    $($field: $ctor,)*
+                    $($field: $crate::combined_early_pass::Timed::new($ctor),)*
+                    profile,
+                }
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                if let Some(profile) = &self.profile {
+                    $(profile.record("early", stringify!($field), &self.$field.pass, self.$field.time);)*
                 }
             }
         }
@@ -80,7 +130,7 @@ macro_rules! combined_early_lint_pass {
             fn get_lints(&self) -> rustc_lint::LintVec {
                 // Reserve at least one slot per pass up front to skip the early reallocations.
                 let mut lints = Vec::with_capacity([$(stringify!($field)),*].len());
-                $(lints.extend(self.$field.get_lints());)*
+                $(lints.extend(self.$field.pass.get_lints());)*
                 lints
             }
         }
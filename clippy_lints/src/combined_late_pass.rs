@@ -9,20 +9,30 @@
 //! [`Gated`] with a precomputed `active` flag (the same "lint still needs to run"
 //! predicate `rustc_lint::late` uses). Disabled passes are skipped by a branch
 //! rather than dropped from a `Vec`, keeping clippy's allow-by-default fast path.
+//!
+//! With `CLIPPY_PROFILE`, each method instead takes a second copy of the forwards
+//! which also time every call, so the usual path pays a single branch per method.
 
 use rustc_lint::{LintPass, LintVec};
+use std::time::Duration;
 
 /// A pass paired with its precomputed "still needs to run" flag.
 pub struct Gated<P> {
     pub(crate) active: bool,
     pub(crate) pass: P,
+    /// The time spent in the pass, only measured when profiling.
+    pub(crate) time: Duration,
 }
 
 impl<P: LintPass> Gated<P> {
     #[inline]
     pub fn new<F: Fn(&LintVec) -> bool>(is_active: &F, pass: P) -> Self {
         let active = is_active(&pass.get_lints());
-        Gated { active, pass }
+        Gated {
+            active,
+            pass,
+            time: Duration::ZERO,
+        }
     }
 }
 
@@ -42,11 +52,28 @@ macro_rules! run_combined_late_lint_pass_field {
     };
 }
 
+/// Like [`run_combined_late_lint_pass_field`], but adds the time of the call to
+/// the field's.
+#[macro_export]
+macro_rules! run_timed_combined_late_lint_pass_field {
+    ($self:ident, $field:ident, $name:ident, ($($arg:expr),* $(,)?)) => {
+        if $self.$field.active {
+            let start = std::time::Instant::now();
+            rustc_lint::LateLintPass::$name(&mut $self.$field.pass, $($arg),*);
+            $self.$field.time += start.elapsed();
+        }
+    };
+}
+
 /// Forward one `check_*` method to every field of the combined pass.
 #[macro_export]
 macro_rules! expand_combined_late_lint_pass_method {
     ([$($field:ident),*], $self:ident, $name:ident, $args:tt) => ({
-        $($crate::run_combined_late_lint_pass_field!($self, $field, $name, $args);)*
+        if $self.profile.is_some() {
+            $($crate::run_timed_combined_late_lint_pass_field!($self, $field, $name, $args);)*
+        } else {
+            $($crate::run_combined_late_lint_pass_field!($self, $field, $name, $args);)*
+        }
     })
 }
 
@@ -67,6 +94,10 @@ macro_rules! expand_combined_late_lint_pass_methods {
 ///
 /// Each entry is `Field: Type = constructor`; `new`'s params (`tcx`, `conf`, ...)
 /// come from the caller so ctor exprs can name them without hygiene trouble.
+/// When a [`PassProfile`] is given, the field times are recorded in it when the
+/// combined pass is dropped.
+///
+/// [`PassProfile`]: crate::pass_profile::PassProfile
 #[macro_export]
 macro_rules! combined_late_lint_pass {
     (
@@ -76,12 +107,26 @@ macro_rules! combined_late_lint_pass {
         #[allow(non_snake_case)]
         pub struct $name<'tcx> {
             $($field: $crate::combined_late_pass::Gated<$fty>,)*
+            profile: Option<$crate::pass_profile::PassProfile>,
         }
 
         impl<'tcx> $name<'tcx> {
-            pub fn new<F: Fn(&rustc_lint::LintVec) -> bool>($($pname: $pty,)* is_active: &F) -> Self {
+            pub fn new<F: Fn(&rustc_lint::LintVec) -> bool>(
+                $($pname: $pty,)*
+                is_active: &F,
+                profile: Option<$crate::pass_profile::PassProfile>,
+            ) -> Self {
                 Self {
                     $($field: $crate::combined_late_pass::Gated::new(is_active, $ctor),)*
+                    profile,
+                }
+            }
+        }
+
+        impl<'tcx> Drop for $name<'tcx> {
+            fn drop(&mut self) {
+                if let Some(profile) = &self.profile {
+                    $(profile.record("late", stringify!($field), &self.$field.pass, self.$field.time);)*
                 }
             }
         }
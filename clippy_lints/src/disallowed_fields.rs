@@ -1,5 +1,5 @@
 use clippy_config::Conf;
-use clippy_config::types::{DisallowedPath, create_disallowed_map};
+use clippy_config::types::{DisallowedPath, UnusedDisallowedPaths, create_disallowed_map};
 use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::paths::PathNS;
 use clippy_utils::ty::get_field_def_id_by_name;
@@ -9,6 +9,7 @@ use rustc_hir::{Expr, ExprKind, Pat, PatKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::TyCtxt;
 use rustc_session::impl_lint_pass;
+use rustc_span::Symbol;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -59,7 +60,8 @@ declare_clippy_lint! {
 impl_lint_pass!(DisallowedFields => [DISALLOWED_FIELDS]);
 
 pub struct DisallowedFields {
-    disallowed: DefIdMap<(&'static str, &'static DisallowedPath)>,
+    disallowed: DefIdMap<(Symbol, &'static DisallowedPath)>,
+    unused: UnusedDisallowedPaths,
 }
 
 impl DisallowedFields {
@@ -72,7 +74,12 @@ impl DisallowedFields {
             "field",
             false,
         );
-        Self { disallowed }
+        let unused = UnusedDisallowedPaths::new(
+            conf.warn_unused_disallowed_paths,
+            &conf.disallowed_fields,
+            disallowed.items().map(|(_, entry)| entry),
+        );
+        Self { disallowed, unused }
     }
 }
 
@@ -95,6 +102,10 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedFields {
             _ => return,
         };
         if let Some(&(path, disallowed_path)) = self.disallowed.get(&id) {
+            self.unused.mark_used(disallowed_path);
+            if disallowed_path.is_allowed_at(cx.tcx, expr.hir_id) {
+                return;
+            }
             span_lint_and_then(
                 cx,
                 DISALLOWED_FIELDS,
@@ -121,13 +132,16 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedFields {
                         }
                     }) && let Some(&(path, disallowed_path)) = self.disallowed.get(&def_id)
                     {
-                        span_lint_and_then(
-                            cx,
-                            DISALLOWED_FIELDS,
-                            field.span,
-                            format!("use of a disallowed field `{path}`"),
-                            disallowed_path.diag_amendment(field.span),
-                        );
+                        self.unused.mark_used(disallowed_path);
+                        if !disallowed_path.is_allowed_at(cx.tcx, pat.hir_id) {
+                            span_lint_and_then(
+                                cx,
+                                DISALLOWED_FIELDS,
+                                field.span,
+                                format!("use of a disallowed field `{path}`"),
+                                disallowed_path.diag_amendment(field.span),
+                            );
+                        }
                     }
                 }
             },
@@ -144,17 +158,24 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedFields {
                         }
                     }) && let Some(&(path, disallowed_path)) = self.disallowed.get(&def_id)
                     {
-                        span_lint_and_then(
-                            cx,
-                            DISALLOWED_FIELDS,
-                            field.span,
-                            format!("use of a disallowed field `{path}`"),
-                            disallowed_path.diag_amendment(field.span),
-                        );
+                        self.unused.mark_used(disallowed_path);
+                        if !disallowed_path.is_allowed_at(cx.tcx, pat.hir_id) {
+                            span_lint_and_then(
+                                cx,
+                                DISALLOWED_FIELDS,
+                                field.span,
+                                format!("use of a disallowed field `{path}`"),
+                                disallowed_path.diag_amendment(field.span),
+                            );
+                        }
                     }
                 }
             },
             _ => {},
         }
     }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        self.unused.emit(cx.tcx, "field");
+    }
 }
@@ -1,5 +1,5 @@
 use clippy_config::Conf;
-use clippy_config::types::{DisallowedPath, create_disallowed_map};
+use clippy_config::types::{DisallowedPath, UnusedDisallowedPaths, create_disallowed_map};
 use clippy_utils::diagnostics::{span_lint_and_then, span_lint_hir_and_then};
 use clippy_utils::macros::macro_backtrace;
 use clippy_utils::paths::PathNS;
@@ -13,7 +13,7 @@ use rustc_hir::{
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::TyCtxt;
 use rustc_session::impl_lint_pass;
-use rustc_span::{ExpnId, MacroKind, Span};
+use rustc_span::{ExpnId, MacroKind, Span, Symbol};
 
 use crate::utils::attr_collector::AttrStorage;
 
@@ -42,7 +42,9 @@ declare_clippy_lint! {
     ///     { path = "serde::Serialize", reason = "no serializing" },
     ///     # This would normally error if the path is incorrect, but with `allow-invalid` = `true`,
     ///     # it will be silently ignored
-    ///     { path = "std::invalid_macro", reason = "use alternative instead", allow-invalid = true }
+    ///     { path = "std::invalid_macro", reason = "use alternative instead", allow-invalid = true },
+    ///     # `allowed-in` lists the crates and modules where the macro can be used anyway.
+    ///     { path = "std::eprintln", allowed-in = ["my_cli", "crate::cli"] },
     /// ]
     /// ```
     /// ```no_run
@@ -66,7 +68,8 @@ declare_clippy_lint! {
 impl_lint_pass!(DisallowedMacros => [DISALLOWED_MACROS]);
 
 pub struct DisallowedMacros {
-    disallowed: DefIdMap<(&'static str, &'static DisallowedPath)>,
+    disallowed: DefIdMap<(Symbol, &'static DisallowedPath)>,
+    unused: UnusedDisallowedPaths,
     seen: FxHashSet<ExpnId>,
     // Track the most recently seen node that can have a `derive` attribute.
     // Needed to use the correct lint level.
@@ -87,8 +90,14 @@ impl DisallowedMacros {
             "macro",
             false,
         );
+        let unused = UnusedDisallowedPaths::new(
+            conf.warn_unused_disallowed_paths,
+            &conf.disallowed_macros,
+            disallowed.items().map(|(_, entry)| entry),
+        );
         Self {
             disallowed,
+            unused,
             seen: FxHashSet::default(),
             derive_src: None,
             early_macro_cache,
@@ -106,6 +115,10 @@ impl DisallowedMacros {
             }
 
             if let Some(&(path, disallowed_path)) = self.disallowed.get(&mac.def_id) {
+                self.unused.mark_used(disallowed_path);
+                if disallowed_path.is_allowed_at(cx.tcx, cx.last_node_with_lint_attrs) {
+                    continue;
+                }
                 let msg = format!("use of a disallowed macro `{path}`");
                 let add_note = disallowed_path.diag_amendment(mac.span);
                 if matches!(mac.kind, MacroKind::Derive)
@@ -189,4 +202,8 @@ impl LateLintPass<'_> for DisallowedMacros {
     fn check_path(&mut self, cx: &LateContext<'_>, path: &Path<'_>, _: HirId) {
         self.check(cx, path.span, None);
     }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'_>) {
+        self.unused.emit(cx.tcx, "macro");
+    }
 }
@@ -1,5 +1,5 @@
 use clippy_config::Conf;
-use clippy_config::types::{DisallowedPath, create_disallowed_map};
+use clippy_config::types::{DisallowedPath, UnusedDisallowedPaths, create_disallowed_map};
 use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::paths::PathNS;
 use rustc_hir::def::{CtorKind, DefKind, Res};
@@ -8,6 +8,7 @@ use rustc_hir::{Expr, ExprKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::TyCtxt;
 use rustc_session::impl_lint_pass;
+use rustc_span::Symbol;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -37,6 +38,16 @@ declare_clippy_lint! {
     ///     # This would normally error if the path is incorrect, but with `allow-invalid` = `true`,
     ///     # it will be silently ignored
     ///     { path = "std::fs::InvalidPath", reason = "use alternative instead", allow-invalid = true },
+    ///     # The last segment can contain the `*` and `?` wildcards to disallow several methods at
+    ///     # once. The methods of a trait are disallowed for every implementor.
+    ///     { path = "std::env::set_*", reason = "the environment is shared with other threads" },
+    ///     "std::io::Write::*",
+    ///     # `for-types` only disallows the method with some types, among `Self` and the types of
+    ///     # its generic arguments, e.g. collecting into a `HashSet`.
+    ///     { path = "std::iter::Iterator::collect", for-types = ["std::collections::HashSet"] },
+    ///     # `allowed-in` lists the crates and modules where the method can be used anyway. A
+    ///     # module path allows the module and the modules it contains.
+    ///     { path = "std::process::exit", allowed-in = ["my_cli", "crate::cli"] },
     /// ]
     /// ```
     ///
@@ -64,7 +75,8 @@ declare_clippy_lint! {
 impl_lint_pass!(DisallowedMethods => [DISALLOWED_METHODS]);
 
 pub struct DisallowedMethods {
-    disallowed: DefIdMap<(&'static str, &'static DisallowedPath)>,
+    disallowed: DefIdMap<(Symbol, &'static DisallowedPath)>,
+    unused: UnusedDisallowedPaths,
 }
 
 impl DisallowedMethods {
@@ -82,7 +94,12 @@ impl DisallowedMethods {
             "function",
             false,
         );
-        Self { disallowed }
+        let unused = UnusedDisallowedPaths::new(
+            conf.warn_unused_disallowed_paths,
+            &conf.disallowed_methods,
+            disallowed.items().map(|(_, entry)| entry),
+        );
+        Self { disallowed, unused }
     }
 }
 
@@ -98,7 +115,13 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedMethods {
             },
             _ => return,
         };
-        if let Some(&(path, disallowed_path)) = self.disallowed.get(&id) {
+        if let Some(&(path, disallowed_path)) = self.disallowed.get(&id)
+            && disallowed_path.applies_to_args(cx.tcx, cx.typeck_results().node_args(expr.hir_id))
+        {
+            self.unused.mark_used(disallowed_path);
+            if disallowed_path.is_allowed_at(cx.tcx, expr.hir_id) {
+                return;
+            }
             span_lint_and_then(
                 cx,
                 DISALLOWED_METHODS,
@@ -108,4 +131,8 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedMethods {
             );
         }
     }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        self.unused.emit(cx.tcx, "function");
+    }
 }
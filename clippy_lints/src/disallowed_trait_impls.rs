@@ -0,0 +1,123 @@
+use clippy_config::Conf;
+use clippy_config::types::{DisallowedPathWithoutReplacement, UnusedDisallowedPaths, create_disallowed_map};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::paths::PathNS;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefIdMap;
+use rustc_hir::{Impl, ImplPolarity, Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::impl_lint_pass;
+use rustc_span::Symbol;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Denies the implementations of the traits configured in clippy.toml, whether they are
+    /// written manually or derived.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// traits are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Some traits shouldn't be implemented in a codebase, or only for some types. For example,
+    /// `Clone` may be undesirable for types holding handles that must stay unique, or `Debug`
+    /// for types holding secrets.
+    ///
+    /// ### Example:
+    /// An example clippy.toml configuration:
+    /// ```toml
+    /// # clippy.toml
+    /// disallowed-trait-impls = [
+    ///     # Can use a string as the path of the disallowed trait.
+    ///     "std::ops::Deref",
+    ///     # When using an inline table, can add a `reason` for why the trait
+    ///     # shouldn't be implemented.
+    ///     { path = "std::clone::Clone", reason = "handles must stay unique", for-types = ["crate::handle::*"] },
+    ///     # This would normally error if the path is incorrect, but with `allow-invalid` = `true`,
+    ///     # it will be silently ignored
+    ///     { path = "serde::Serialize", allow-invalid = true, allowed-in = ["crate::api"] },
+    /// ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// mod handle {
+    ///     #[derive(Clone)]
+    ///     pub struct FileHandle(i32);
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// mod handle {
+    ///     pub struct FileHandle(i32);
+    /// }
+    /// ```
+    #[clippy::version = "1.98.0"]
+    pub DISALLOWED_TRAIT_IMPLS,
+    style,
+    "implementation of disallowed traits"
+}
+
+impl_lint_pass!(DisallowedTraitImpls => [DISALLOWED_TRAIT_IMPLS]);
+
+pub struct DisallowedTraitImpls {
+    def_ids: DefIdMap<(Symbol, &'static DisallowedPathWithoutReplacement)>,
+    unused: UnusedDisallowedPaths<false>,
+}
+
+impl DisallowedTraitImpls {
+    pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
+        let (def_ids, _) = create_disallowed_map(
+            tcx,
+            &conf.disallowed_trait_impls,
+            PathNS::Type,
+            |def_kind| matches!(def_kind, DefKind::Trait),
+            "trait",
+            false,
+        );
+        let unused = UnusedDisallowedPaths::new(
+            conf.warn_unused_disallowed_paths,
+            &conf.disallowed_trait_impls,
+            def_ids.items().map(|(_, entry)| entry),
+        );
+        Self { def_ids, unused }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for DisallowedTraitImpls {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if let ItemKind::Impl(Impl {
+            of_trait: Some(of_trait),
+            ..
+        }) = item.kind
+            && of_trait.polarity == ImplPolarity::Positive
+            && let Some(trait_id) = of_trait.trait_ref.trait_def_id()
+            && let Some(&(path, disallowed_path)) = self.def_ids.get(&trait_id)
+            && disallowed_path.applies_to_impl_for(
+                cx.tcx,
+                cx.tcx.type_of(item.owner_id).instantiate_identity().skip_norm_wip(),
+            )
+        {
+            self.unused.mark_used(disallowed_path);
+            if disallowed_path.is_allowed_at(cx.tcx, item.hir_id()) {
+                return;
+            }
+            // Derived implementations are reported on the trait name in the `derive` attribute.
+            let span = if item.span.from_expansion() {
+                item.span.source_callsite()
+            } else {
+                of_trait.trait_ref.path.span
+            };
+            span_lint_and_then(
+                cx,
+                DISALLOWED_TRAIT_IMPLS,
+                span,
+                format!("implementation of a disallowed trait `{path}`"),
+                disallowed_path.diag_amendment(span),
+            );
+        }
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        self.unused.emit(cx.tcx, "trait");
+    }
+}
@@ -1,15 +1,16 @@
 use clippy_config::Conf;
-use clippy_config::types::{DisallowedPath, create_disallowed_map};
+use clippy_config::types::{DisallowedPath, UnusedDisallowedPaths, create_disallowed_map};
 use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::paths::PathNS;
 use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::unord::UnordItems;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::DefIdMap;
-use rustc_hir::{AmbigArg, Item, ItemKind, PolyTraitRef, PrimTy, Ty, TyKind, UseKind};
+use rustc_hir::{AmbigArg, HirId, Item, ItemKind, PolyTraitRef, PrimTy, Ty, TyKind, UseKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::TyCtxt;
 use rustc_session::impl_lint_pass;
-use rustc_span::Span;
+use rustc_span::{Span, Symbol};
 
 declare_clippy_lint! {
     /// ### What it does
@@ -37,7 +38,9 @@ declare_clippy_lint! {
     ///     { path = "std::sync::Mutex", reason = "prefer faster & simpler non-poisonable mutex", replacement = "parking_lot::Mutex" },
     ///     # This would normally error if the path is incorrect, but with `allow-invalid` = `true`,
     ///     # it will be silently ignored
-    ///     { path = "std::invalid::Type", reason = "use alternative instead", allow-invalid = true }
+    ///     { path = "std::invalid::Type", reason = "use alternative instead", allow-invalid = true },
+    ///     # The last segment can contain the `*` and `?` wildcards to disallow several types at once.
+    ///     "std::sync::mpsc::*",
     /// ]
     /// ```
     ///
@@ -60,8 +63,9 @@ declare_clippy_lint! {
 impl_lint_pass!(DisallowedTypes => [DISALLOWED_TYPES]);
 
 pub struct DisallowedTypes {
-    def_ids: DefIdMap<(&'static str, &'static DisallowedPath)>,
-    prim_tys: FxHashMap<PrimTy, (&'static str, &'static DisallowedPath)>,
+    def_ids: DefIdMap<(Symbol, &'static DisallowedPath)>,
+    prim_tys: FxHashMap<PrimTy, (Symbol, &'static DisallowedPath)>,
+    unused: UnusedDisallowedPaths,
 }
 
 impl DisallowedTypes {
@@ -74,15 +78,32 @@ impl DisallowedTypes {
             "type",
             true,
         );
-        Self { def_ids, prim_tys }
+        #[expect(rustc::potential_query_instability, reason = "only collected into a set")]
+        let unused = UnusedDisallowedPaths::new(
+            conf.warn_unused_disallowed_paths,
+            &conf.disallowed_types,
+            def_ids
+                .items()
+                .map(|(_, entry)| entry)
+                .chain(UnordItems::new(prim_tys.values())),
+        );
+        Self {
+            def_ids,
+            prim_tys,
+            unused,
+        }
     }
 
-    fn check_res_emit(&self, cx: &LateContext<'_>, res: &Res, span: Span) {
+    fn check_res_emit(&mut self, cx: &LateContext<'_>, res: &Res, span: Span, hir_id: HirId) {
         let (path, disallowed_path) = match res {
             Res::Def(_, did) if let Some(&x) = self.def_ids.get(did) => x,
             Res::PrimTy(prim) if let Some(&x) = self.prim_tys.get(prim) => x,
             _ => return,
         };
+        self.unused.mark_used(disallowed_path);
+        if disallowed_path.is_allowed_at(cx.tcx, hir_id) {
+            return;
+        }
         span_lint_and_then(
             cx,
             DISALLOWED_TYPES,
@@ -111,17 +132,26 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedTypes {
         if let ItemKind::Use(path, UseKind::Single(_)) = &item.kind
             && let Some(res) = path.res.type_ns
         {
-            self.check_res_emit(cx, &res, item.span);
+            self.check_res_emit(cx, &res, item.span, item.hir_id());
         }
     }
 
     fn check_ty(&mut self, cx: &LateContext<'tcx>, ty: &'tcx Ty<'tcx, AmbigArg>) {
         if let TyKind::Path(path) = &ty.kind {
-            self.check_res_emit(cx, &cx.qpath_res(path, ty.hir_id), ty.span);
+            self.check_res_emit(cx, &cx.qpath_res(path, ty.hir_id), ty.span, ty.hir_id);
         }
     }
 
     fn check_poly_trait_ref(&mut self, cx: &LateContext<'tcx>, poly: &'tcx PolyTraitRef<'tcx>) {
-        self.check_res_emit(cx, &poly.trait_ref.path.res, poly.trait_ref.path.span);
+        self.check_res_emit(
+            cx,
+            &poly.trait_ref.path.res,
+            poly.trait_ref.path.span,
+            poly.trait_ref.hir_ref_id,
+        );
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        self.unused.emit(cx.tcx, "type");
     }
 }
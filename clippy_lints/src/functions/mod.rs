@@ -15,7 +15,7 @@ use clippy_utils::paths::{PathNS, lookup_path_str};
 use rustc_ast::{self as ast, visit};
 use rustc_hir as hir;
 use rustc_hir::intravisit;
-use rustc_lint::{EarlyContext, EarlyLintPass, LateContext, LateLintPass};
+use rustc_lint::{EarlyContext, EarlyLintPass, LateContext, LateLintPass, LintContext};
 use rustc_middle::ty::TyCtxt;
 use rustc_session::{declare_lint_pass, impl_lint_pass};
 use rustc_span::Span;
@@ -497,8 +497,9 @@ impl EarlyLintPass for EarlyFunctions {
 }
 
 pub struct Functions {
-    too_many_arguments_threshold: u64,
-    too_many_lines_threshold: u64,
+    /// Used to look up `too-many-arguments-threshold` and `too-many-lines-threshold`, which can be
+    /// overridden per file.
+    conf: &'static Conf,
     large_error_threshold: u64,
     large_error_ignored: DefIdSet,
     avoid_breaking_exported_api: bool,
@@ -511,8 +512,7 @@ pub struct Functions {
 impl Functions {
     pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
         Self {
-            too_many_arguments_threshold: conf.too_many_arguments_threshold,
-            too_many_lines_threshold: conf.too_many_lines_threshold,
+            conf,
             large_error_threshold: conf.large_error_threshold,
             large_error_ignored: conf
                 .large_error_ignored
@@ -541,8 +541,9 @@ impl<'tcx> LateLintPass<'tcx> for Functions {
         def_id: LocalDefId,
     ) {
         let hir_id = cx.tcx.local_def_id_to_hir_id(def_id);
-        too_many_arguments::check_fn(cx, kind, decl, hir_id, def_id, self.too_many_arguments_threshold);
-        too_many_lines::check_fn(cx, kind, body, span, def_id, self.too_many_lines_threshold);
+        let conf = self.conf.for_span(cx.sess(), span);
+        too_many_arguments::check_fn(cx, kind, decl, hir_id, def_id, conf.too_many_arguments_threshold);
+        too_many_lines::check_fn(cx, kind, body, span, def_id, conf.too_many_lines_threshold);
         not_unsafe_ptr_arg_deref::check_fn(cx, kind, decl, body, def_id);
         misnamed_getters::check_fn(cx, kind, decl, body, span);
         impl_trait_in_params::check_fn(cx, &kind, body, hir_id);
@@ -583,7 +584,8 @@ impl<'tcx> LateLintPass<'tcx> for Functions {
     }
 
     fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::TraitItem<'_>) {
-        too_many_arguments::check_trait_item(cx, item, self.too_many_arguments_threshold);
+        let conf = self.conf.for_span(cx.sess(), item.span);
+        too_many_arguments::check_trait_item(cx, item, conf.too_many_arguments_threshold);
         not_unsafe_ptr_arg_deref::check_trait_item(cx, item);
         must_use::check_trait_item(cx, item);
         result::check_trait_item(
@@ -60,6 +60,7 @@ mod combined_late_pass;
 
 pub mod declared_lints;
 pub mod deprecated_lints;
+pub mod pass_profile;
 
 // begin lints modules, do not remove this comment, it's used in `update_lints`
 mod absolute_paths;
@@ -110,6 +111,7 @@ mod disallowed_macros;
 mod disallowed_methods;
 mod disallowed_names;
 mod disallowed_script_idents;
+mod disallowed_trait_impls;
 mod disallowed_types;
 mod doc;
 mod double_parens;
@@ -413,23 +415,59 @@ mod zombie_processes;
 
 use clippy_config::{Conf, get_configuration_metadata, sanitize_explanation};
 use clippy_utils::macros::FormatArgsStorage;
-use rustc_data_structures::fx::FxHashSet;
+use pass_profile::PassProfile;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_lint::Lint;
 use rustc_middle::ty::TyCtxt;
 use utils::attr_collector::AttrStorage;
 
-pub fn explain(name: &str) -> i32 {
+/// Prints the documentation of the lint `name` followed by its group, default level, version and
+/// configuration options. The values of the options are taken from `conf`, the configuration of
+/// the current directory, if it could be read.
+pub fn explain(name: &str, conf: Option<&Conf>) -> i32 {
     let target = format!("clippy::{}", name.to_ascii_uppercase());
 
     if let Some(info) = declared_lints::LINTS.iter().find(|info| info.lint.name == target) {
         println!("{}", sanitize_explanation(info.explanation));
+        println!("### Lint information\n");
+        println!("- Group: `{}`", info.category.name());
+        println!("- Default level: `{}`", info.lint.default_level.as_str());
+        println!("- Added in: {}", info.version);
+
         // Check if the lint has configuration
-        let mut mdconf = get_configuration_metadata();
+        let values = conf.map(Conf::serialized_values);
+        let mut mdconf: Vec<_> = get_configuration_metadata().into_iter().enumerate().collect();
         let name = name.to_ascii_lowercase();
-        mdconf.retain(|cconf| cconf.lints.contains(&&*name));
+        mdconf.retain(|(_, cconf)| cconf.lints.contains(&&*name));
+        // The current value of the option at `index`, e.g. `None` for an unset `msrv`
+        let value = |index: usize| match values.as_ref().map(|values| &values[index].1) {
+            Some(Some(value)) => format!("`{value}`"),
+            Some(None) => "not set".to_string(),
+            None => "unknown".to_string(),
+        };
+        if let Some((index, _)) = mdconf.iter().find(|(_, cconf)| cconf.name == "msrv") {
+            if conf.is_some() {
+                println!(
+                    "- Respects the `msrv` configuration option, which is currently {}",
+                    value(*index)
+                );
+            } else {
+                println!("- Respects the `msrv` configuration option");
+            }
+        }
+        println!();
+
         if !mdconf.is_empty() {
             println!("### Configuration for {}:\n", info.lint.name_lower());
-            for conf in mdconf {
+            if conf.is_some() {
+                println!("| Option | Value | Default |");
+                println!("| ------ | ----- | ------- |");
+                for (index, cconf) in &mdconf {
+                    println!("| `{}` | {} | `{}` |", cconf.name, value(*index), cconf.default);
+                }
+                println!();
+            }
+            for (_, conf) in mdconf {
                 println!("{conf}");
             }
         }
@@ -440,10 +478,96 @@ pub fn explain(name: &str) -> i32 {
     }
 }
 
-/// Register all lints and lint groups with the rustc lint store
+/// An entry of `cargo clippy --list-lints`.
+#[derive(serde::Serialize)]
+struct ListedLint {
+    id: String,
+    group: &'static str,
+    level: &'static str,
+    version: &'static str,
+    /// The configuration options affecting the lint, including `msrv`.
+    config: Vec<String>,
+    /// Whether the lint depends on the `msrv` configuration, i.e. whether it's disabled or changes
+    /// its suggestions for older Rust versions.
+    msrv_gated: bool,
+    /// The best applicability of the lint suggestions in Clippy's test suite, e.g.
+    /// `MachineApplicable`, as on the lint list website.
+    applicability: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deprecation_reason: Option<&'static str>,
+    docs: String,
+}
+
+/// Prints every lint with its group, default level, version and configuration options, as JSON
+/// if `json` is set or as one line per lint otherwise.
+///
+/// # Panics
+///
+/// Panics if the applicabilities collected by `cargo collect-metadata` aren't valid JSON.
+pub fn list_lints(json: bool) -> i32 {
+    let configs = get_configuration_metadata();
+    // Collected by `cargo collect-metadata` along with the lint list website
+    let applicabilities: FxHashMap<&str, &'static str> =
+        serde_json::from_str(include_str!("applicabilities.json")).expect("the applicabilities are valid JSON");
+    let lints = declared_lints::LINTS.iter().map(|info| {
+        let id = info.name_lower();
+        let config: Vec<String> = configs
+            .iter()
+            .filter(|conf| conf.deprecation_reason.is_none() && conf.lints.contains(&id.as_str()))
+            .map(|conf| conf.name.clone())
+            .collect();
+        let msrv_gated = config.iter().any(|name| name == "msrv");
+        let applicability = applicabilities.get(id.as_str()).copied().unwrap_or("Unspecified");
+        ListedLint {
+            id,
+            group: info.category.name(),
+            level: info.lint.default_level.as_str(),
+            version: info.version,
+            config,
+            msrv_gated,
+            applicability,
+            deprecation_reason: None,
+            docs: sanitize_explanation(info.explanation),
+        }
+    });
+    let deprecated = deprecated_lints::DEPRECATED
+        .iter()
+        .zip(deprecated_lints::DEPRECATED_VERSION)
+        .map(|(&(name, reason), &version)| ListedLint {
+            id: name.strip_prefix("clippy::").unwrap_or(name).to_owned(),
+            group: "deprecated",
+            level: "none",
+            version,
+            config: Vec::new(),
+            msrv_gated: false,
+            applicability: "Unspecified",
+            deprecation_reason: Some(reason),
+            docs: String::new(),
+        });
+    let mut lints: Vec<ListedLint> = lints.chain(deprecated).collect();
+    lints.sort_by(|a, b| a.id.cmp(&b.id));
+
+    if json {
+        match serde_json::to_string_pretty(&lints) {
+            Ok(lints) => println!("{lints}"),
+            Err(e) => {
+                eprintln!("error: couldn't serialize the lints: {e}");
+                return 1;
+            },
+        }
+    } else {
+        for lint in lints {
+            println!("clippy::{:<50} {:<12} {}", lint.id, lint.group, lint.level);
+        }
+    }
+    0
+}
+
+/// Register all lints and lint groups with the rustc lint store. The time spent in each pass is
+/// recorded in `profile` if given.
 ///
 /// Used in `./src/driver.rs`.
-pub fn register_lint_passes(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
+pub fn register_lint_passes(store: &mut rustc_lint::LintStore, conf: &'static Conf, profile: Option<PassProfile>) {
     for (old_name, new_name) in deprecated_lints::RENAMED {
         store.register_renamed(old_name, new_name);
     }
@@ -462,8 +586,14 @@ pub fn register_lint_passes(store: &mut rustc_lint::LintStore, conf: &'static Co
     {
         let format_args = format_args_storage.clone();
         let attrs = attr_storage.clone();
+        let profile = profile.clone();
         store.early_passes.push(Box::new(move || {
-            Box::new(CombinedEarlyLintPass::new(conf, format_args.clone(), attrs.clone()))
+            Box::new(CombinedEarlyLintPass::new(
+                conf,
+                format_args.clone(),
+                attrs.clone(),
+                profile.clone(),
+            ))
         }));
     }
 
@@ -481,6 +611,7 @@ pub fn register_lint_passes(store: &mut rustc_lint::LintStore, conf: &'static Co
             format_args_storage.clone(),
             attr_storage.clone(),
             &is_active,
+            profile.clone(),
         ))
     }));
 }
@@ -862,6 +993,7 @@ rustc_lint::late_lint_methods!(
         ManualAssertEq: manual_assert_eq::ManualAssertEq = manual_assert_eq::ManualAssertEq,
         WithCapacityZero: with_capacity_zero::WithCapacityZero = with_capacity_zero::WithCapacityZero,
         RefPatterns: ref_patterns::RefPatterns = ref_patterns::RefPatterns,
+        DisallowedTraitImpls: disallowed_trait_impls::DisallowedTraitImpls = disallowed_trait_impls::DisallowedTraitImpls::new(tcx, conf),
         // add late passes here, used by `cargo dev new_lint`
     ]]
 );
@@ -0,0 +1,111 @@
+//! Wall time spent in each lint pass, recorded when `CLIPPY_PROFILE` is set.
+//!
+//! The combined passes (see [`combined_early_pass`] and [`combined_late_pass`]) time every
+//! `check_*` call of their fields in that case, and record the totals here when they're dropped
+//! at the end of the lint run.
+//!
+//! [`combined_early_pass`]: crate::combined_early_pass
+//! [`combined_late_pass`]: crate::combined_late_pass
+
+use rustc_lint::LintPass;
+use serde_json::json;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+/// The time spent in the lint passes of the crate being compiled. Clones share the same records.
+#[derive(Clone, Default)]
+pub struct PassProfile {
+    passes: Arc<Mutex<Vec<PassTime>>>,
+}
+
+struct PassTime {
+    /// `early` or `late`
+    kind: &'static str,
+    name: &'static str,
+    /// The lints emitted by the pass, e.g. `clippy::needless_return`
+    lints: Vec<String>,
+    time: Duration,
+}
+
+fn millis(time: Duration) -> f64 {
+    time.as_secs_f64() * 1000.0
+}
+
+impl PassProfile {
+    /// Records the time spent in `pass`. The passes which didn't run are left out.
+    pub(crate) fn record(&self, kind: &'static str, name: &'static str, pass: &impl LintPass, time: Duration) {
+        if time.is_zero() {
+            return;
+        }
+        self.passes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(PassTime {
+                kind,
+                name,
+                lints: pass.get_lints().iter().map(|lint| lint.name_lower()).collect(),
+                time,
+            });
+    }
+
+    /// Sorts the passes from the slowest one, and returns them with their total time.
+    fn sorted(&self) -> (Vec<PassTime>, Duration) {
+        let mut passes = std::mem::take(&mut *self.passes.lock().unwrap_or_else(PoisonError::into_inner));
+        passes.sort_by(|a, b| b.time.cmp(&a.time).then(a.name.cmp(b.name)));
+        let total = passes.iter().map(|pass| pass.time).sum();
+        (passes, total)
+    }
+
+    /// Takes the records as a table sorted from the slowest pass.
+    pub fn take_table(&self, crate_name: &str, test: bool) -> String {
+        let (passes, total) = self.sorted();
+        let mut table = format!(
+            "Clippy lint pass times of `{crate_name}`{}, {:.1}ms in total:\n",
+            if test { " (test)" } else { "" },
+            millis(total)
+        );
+        for pass in &passes {
+            let share = if total.is_zero() {
+                0.0
+            } else {
+                pass.time.as_secs_f64() / total.as_secs_f64() * 100.0
+            };
+            writeln!(
+                table,
+                "{:>10.3}ms {share:>5.1}%  {:<5} {:<40} {}",
+                millis(pass.time),
+                pass.kind,
+                pass.name,
+                pass.lints.join(", ")
+            )
+            .unwrap();
+        }
+        table
+    }
+
+    /// Takes the records as a JSON object on a single line, with the passes sorted from the
+    /// slowest one. `test` tells whether the crate is compiled as a test harness, which Cargo does
+    /// for the unit tests.
+    pub fn take_json(&self, crate_name: &str, test: bool) -> String {
+        let (passes, total) = self.sorted();
+        let passes: Vec<_> = passes
+            .iter()
+            .map(|pass| {
+                json!({
+                    "kind": pass.kind,
+                    "name": pass.name,
+                    "time_ms": millis(pass.time),
+                    "lints": pass.lints,
+                })
+            })
+            .collect();
+        json!({
+            "crate": crate_name,
+            "test": test,
+            "total_ms": millis(total),
+            "passes": passes,
+        })
+        .to_string()
+    }
+}
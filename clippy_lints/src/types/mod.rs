@@ -17,7 +17,7 @@ use rustc_hir::{
     Body, FnDecl, FnRetTy, GenericArg, ImplItem, ImplItemKind, Item, ItemKind, LetStmt, MutTy, QPath, TraitFn,
     TraitItem, TraitItemKind, TyKind,
 };
-use rustc_lint::{LateContext, LateLintPass};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
 use rustc_session::impl_lint_pass;
 use rustc_span::Span;
 use rustc_span::def_id::LocalDefId;
@@ -405,8 +405,7 @@ impl_lint_pass!(Types => [
 ]);
 
 pub struct Types {
-    vec_box_size_threshold: u64,
-    type_complexity_threshold: u64,
+    conf: &'static Conf,
     avoid_breaking_exported_api: bool,
 }
 
@@ -545,8 +544,7 @@ impl<'tcx> LateLintPass<'tcx> for Types {
 impl Types {
     pub fn new(conf: &'static Conf) -> Self {
         Self {
-            vec_box_size_threshold: conf.vec_box_size_threshold,
-            type_complexity_threshold: conf.type_complexity_threshold,
+            conf,
             avoid_breaking_exported_api: conf.avoid_breaking_exported_api,
         }
     }
@@ -583,7 +581,8 @@ impl Types {
             return;
         }
 
-        if !context.is_nested_call && type_complexity::check(cx, hir_ty, self.type_complexity_threshold) {
+        let conf = self.conf.for_span(cx.sess(), hir_ty.span);
+        if !context.is_nested_call && type_complexity::check(cx, hir_ty, conf.type_complexity_threshold) {
             return;
         }
 
@@ -603,7 +602,7 @@ impl Types {
                     triggered |= box_collection::check(cx, hir_ty, qpath, def_id);
                     triggered |= redundant_allocation::check(cx, hir_ty, qpath, def_id);
                     triggered |= rc_buffer::check(cx, hir_ty, qpath, def_id);
-                    triggered |= vec_box::check(cx, hir_ty, qpath, def_id, self.vec_box_size_threshold);
+                    triggered |= vec_box::check(cx, hir_ty, qpath, def_id, conf.vec_box_size_threshold);
                     triggered |= option_option::check(cx, hir_ty, qpath, def_id);
                     triggered |= linked_list::check(cx, hir_ty, def_id);
                     triggered |= rc_mutex::check(cx, hir_ty, qpath, def_id);
@@ -8,32 +8,176 @@
 //! Thank you!
 //! ~The `INTERNAL_METADATA_COLLECTOR` lint
 
-use rustc_errors::{Applicability, Diag, DiagCtxtHandle, DiagMessage, Diagnostic, Level, MultiSpan};
+use rustc_errors::{
+    Applicability, Diag, DiagArgValue, DiagCtxtHandle, DiagInner, DiagMessage, Diagnostic, ErrorGuaranteed, Level,
+    MultiSpan, Suggestions,
+};
 #[cfg(debug_assertions)]
-use rustc_errors::{EmissionGuarantee, SubstitutionPart, Suggestions};
+use rustc_errors::{EmissionGuarantee, SubstitutionPart};
 use rustc_hir::HirId;
 use rustc_lint::{LateContext, Lint, LintContext};
+use rustc_middle::lint::LintLevelSource;
+use rustc_session::Session;
 use rustc_span::Span;
 use std::env;
+use std::sync::OnceLock;
+
+/// Returns the URL of the documentation of `lint`, given without the `clippy::` prefix, in the lint
+/// list matching the release channel of Clippy.
+pub fn lint_docs_url(lint: &str) -> String {
+    format!(
+        "https://rust-lang.github.io/rust-clippy/{}/index.html#{lint}",
+        match option_env!("CFG_RELEASE_CHANNEL") {
+            // Clippy version is 0.1.xx
+            //
+            // Always use .0 because we do not generate separate lint doc pages for rust patch releases
+            Some("stable") => concat!("rust-1.", env!("CARGO_PKG_VERSION_PATCH"), ".0"),
+            Some("beta") => "beta",
+            _ => "master",
+        }
+    )
+}
 
 fn docs_link(diag: &mut Diag<'_, ()>, lint: &'static Lint) {
     if env::var("CLIPPY_DISABLE_DOCS_LINKS").is_err()
         && let Some(lint) = lint.name_lower().strip_prefix("clippy::")
     {
-        diag.help(format!(
-            "for further information visit https://rust-lang.github.io/rust-clippy/{}/index.html#{lint}",
-            match option_env!("CFG_RELEASE_CHANNEL") {
-                // Clippy version is 0.1.xx
-                //
-                // Always use .0 because we do not generate separate lint doc pages for rust patch releases
-                Some("stable") => concat!("rust-1.", env!("CARGO_PKG_VERSION_PATCH"), ".0"),
-                Some("beta") => "beta",
-                _ => "master",
-            }
-        ));
+        diag.help(format!("for further information visit {}", lint_docs_url(lint)));
+    }
+}
+
+/// The argument holding the name of the Clippy lint which emitted a diagnostic, e.g.
+/// `clippy::unwrap_used`, as rustc doesn't tell the lint of the diagnostics it emits.
+const CLIPPY_LINT_ARG: &str = "clippy_lint";
+
+/// Returns the name of the Clippy lint which emitted the diagnostic, or `None` for the diagnostics
+/// of rustc.
+pub fn clippy_lint(diag: &DiagInner) -> Option<&str> {
+    match diag.args.get(CLIPPY_LINT_ARG)? {
+        DiagArgValue::Str(lint) => Some(lint),
+        _ => None,
     }
 }
 
+/// Which suggestions `cargo clippy --fix` applies, set by `--fix-only` and `--fix-applicability`.
+///
+/// `cargo fix` applies the machine-applicable suggestions, so the applicability of the other
+/// suggestions is changed before they're emitted, the ones of rustc's diagnostics included.
+#[derive(Debug, Default)]
+pub struct FixFilter {
+    /// The lints whose suggestions are applied, without the `clippy::` prefix, or `None` for all of
+    /// them.
+    pub lints: Option<Vec<String>>,
+    /// Whether to also apply the `MaybeIncorrect` suggestions.
+    pub maybe_incorrect: bool,
+}
+
+impl FixFilter {
+    fn apply(&self, diag: &mut DiagInner) {
+        let selected = self.lints.as_ref().is_none_or(|lints| {
+            clippy_lint(diag)
+                .and_then(|lint| lint.strip_prefix("clippy::"))
+                .is_some_and(|name| lints.iter().any(|lint| lint == name))
+        });
+        let suggestions = match &mut diag.suggestions {
+            Suggestions::Enabled(suggs) => &mut suggs[..],
+            Suggestions::Sealed(suggs) => &mut suggs[..],
+            Suggestions::Disabled => return,
+        };
+        for sugg in suggestions {
+            sugg.applicability = match sugg.applicability {
+                Applicability::MachineApplicable if !selected => Applicability::MaybeIncorrect,
+                Applicability::MaybeIncorrect if selected && self.maybe_incorrect => Applicability::MachineApplicable,
+                applicability => applicability,
+            };
+        }
+    }
+}
+
+static FIX_FILTER: OnceLock<FixFilter> = OnceLock::new();
+
+/// Sets the suggestions to apply for the rest of the compilation. Used in `./src/driver.rs`.
+pub fn set_fix_filter(filter: FixFilter) {
+    let _ = FIX_FILTER.set(filter);
+}
+
+/// Whether a lint diagnostic shouldn't be emitted, e.g. because it's recorded in the baseline of
+/// `cargo clippy --baseline` or points outside of the lines given to `--changed-since`.
+type LintFilter = dyn Fn(&DiagInner) -> bool + Send + Sync;
+
+static LINT_FILTER: OnceLock<Box<LintFilter>> = OnceLock::new();
+
+/// Sets the lint diagnostics to drop for the rest of the compilation, rustc's included. Unlike the
+/// allowed lints, they're dropped once their level is known, so `#[expect]` still works, and rustc
+/// doesn't count them as warnings or errors. Used in `./src/driver.rs`.
+pub fn set_lint_filter(suppresses: impl Fn(&DiagInner) -> bool + Send + Sync + 'static) {
+    let _ = LINT_FILTER.set(Box::new(suppresses));
+}
+
+type TrackDiagnostic = fn(DiagInner, &mut dyn FnMut(DiagInner) -> Option<ErrorGuaranteed>) -> Option<ErrorGuaranteed>;
+
+/// The function rustc passes every diagnostic through, which records them for the incremental
+/// compilation.
+static RUSTC_TRACK_DIAGNOSTIC: OnceLock<&'static TrackDiagnostic> = OnceLock::new();
+
+/// Makes every diagnostic of the rest of the compilation, rustc's included, go through the
+/// [`FixFilter`] and the filter of [`set_lint_filter`] before it's emitted. Used in
+/// `./src/driver.rs` once rustc set up its own tracking of the diagnostics, which still records
+/// them unchanged.
+pub fn track_diagnostics() {
+    static TRACK_DIAGNOSTIC: TrackDiagnostic = track_diagnostic;
+    let rustc_track_diagnostic = rustc_errors::TRACK_DIAGNOSTIC.swap(&TRACK_DIAGNOSTIC);
+    // Only keep the first function, the next ones are this one
+    let _ = RUSTC_TRACK_DIAGNOSTIC.set(rustc_track_diagnostic);
+}
+
+fn track_diagnostic(
+    diag: DiagInner,
+    emit: &mut dyn FnMut(DiagInner) -> Option<ErrorGuaranteed>,
+) -> Option<ErrorGuaranteed> {
+    let rustc_track_diagnostic = RUSTC_TRACK_DIAGNOSTIC.get().expect("`track_diagnostics` was called");
+    rustc_track_diagnostic(diag, &mut |mut diag| {
+        // The other diagnostics can't be dropped, their callers expect them to be emitted
+        if diag.is_lint.is_some() && LINT_FILTER.get().is_some_and(|suppresses| suppresses(&diag)) {
+            return None;
+        }
+        if let Some(filter) = FIX_FILTER.get() {
+            filter.apply(&mut diag);
+        }
+        emit(diag)
+    })
+}
+
+/// Whether a lint is allowed at a span by the configuration, like the `allow` key of the
+/// `[[overrides]]` of `clippy.toml`.
+type AllowedByConf = dyn Fn(&Session, Span, &'static Lint) -> bool + Send + Sync;
+
+static ALLOWED_BY_CONF: OnceLock<Box<AllowedByConf>> = OnceLock::new();
+
+/// Sets the lints allowed by the configuration for the rest of the compilation, which aren't
+/// emitted. Used in `./src/driver.rs`.
+pub fn set_allowed_by_conf(allowed: impl Fn(&Session, Span, &'static Lint) -> bool + Send + Sync + 'static) {
+    let _ = ALLOWED_BY_CONF.set(Box::new(allowed));
+}
+
+/// Whether `lint` is allowed at `sp` by the configuration. It works like an `-A` on the command
+/// line restricted to some files: the attributes written in those files, like `#[expect]`, take
+/// precedence over it, so `level_source` gives where the level of the lint comes from.
+fn allowed_by_conf(
+    sess: &Session,
+    sp: &MultiSpan,
+    lint: &'static Lint,
+    level_source: impl FnOnce() -> LintLevelSource,
+) -> bool {
+    ALLOWED_BY_CONF
+        .get()
+        .zip(sp.primary_span())
+        .is_some_and(|(allowed, span)| {
+            allowed(sess, span, lint)
+                && !matches!(level_source(), LintLevelSource::Node { span, .. } if allowed(sess, span, lint))
+        })
+}
+
 /// Makes sure that a diagnostic is well formed.
 ///
 /// rustc debug asserts a few properties about spans,
@@ -251,6 +395,9 @@ where
     }
 
     let sp = sp.into();
+    if allowed_by_conf(cx.sess(), &sp, lint, || cx.get_lint_level_spec(lint).src) {
+        return;
+    }
     #[expect(clippy::disallowed_methods)]
     cx.emit_span_lint(
         lint,
@@ -260,6 +407,7 @@ where
             diag.span(sp);
             f(diag);
             docs_link(diag, lint);
+            diag.arg(CLIPPY_LINT_ARG, lint.name_lower());
 
             #[cfg(debug_assertions)]
             validate_diag(diag);
@@ -329,6 +477,12 @@ pub fn span_lint_hir_and_then(
     msg: impl Into<DiagMessage>,
     f: impl FnOnce(&mut Diag<'_, ()>),
 ) {
+    let sp = sp.into();
+    if allowed_by_conf(cx.sess(), &sp, lint, || {
+        cx.tcx.lint_level_spec_at_node(lint, hir_id).src
+    }) {
+        return;
+    }
     #[expect(clippy::disallowed_methods)]
     cx.tcx.emit_node_span_lint(
         lint,
@@ -338,6 +492,7 @@ pub fn span_lint_hir_and_then(
             diag.primary_message(msg);
             f(diag);
             docs_link(diag, lint);
+            diag.arg(CLIPPY_LINT_ARG, lint.name_lower());
 
             #[cfg(debug_assertions)]
             validate_diag(diag);
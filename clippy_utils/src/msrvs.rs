@@ -8,7 +8,7 @@ use rustc_lint::LateContext;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::Session;
 use rustc_span::Symbol;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::iter::once;
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -112,6 +112,18 @@ impl<'de> Deserialize<'de> for Msrv {
     }
 }
 
+impl Serialize for Msrv {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.0 {
+            Some(version) => serializer.collect_str(&version),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
 impl Msrv {
     /// Returns the MSRV at the current node
     ///
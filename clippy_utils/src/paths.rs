@@ -196,6 +196,40 @@ pub fn lookup_path(tcx: TyCtxt<'_>, ns: PathNS, path: &[Symbol]) -> Vec<DefId> {
     out
 }
 
+/// Resolves the items of the parent path `parent` whose name satisfies `matches`, e.g. every
+/// function of `std::env` whose name starts with `set_`. The items of the inherent impls of a type,
+/// and the associated items of a trait, count as its children.
+///
+/// This function is even more expensive than [`lookup_path`] and should be used sparingly.
+pub fn lookup_path_matching(
+    tcx: TyCtxt<'_>,
+    ns: PathNS,
+    parent: &[Symbol],
+    matches: impl Fn(Symbol) -> bool,
+) -> Vec<DefId> {
+    let parents = match *parent {
+        [] => return Vec::new(),
+        [root] => find_crates(tcx, root)
+            .iter()
+            .chain(find_primitive_impls(tcx, root))
+            .copied()
+            .collect(),
+        _ => lookup_path(tcx, PathNS::Type, parent),
+    };
+
+    let mut out = Vec::new();
+    for base in parents {
+        let mut names = item_children_names(tcx, base);
+        names.retain(|&name| matches(name));
+        names.sort_unstable();
+        names.dedup();
+        for name in names {
+            lookup_with_base(tcx, base, ns, &[name], &mut out);
+        }
+    }
+    out
+}
+
 /// Finds the crates called `name`, may be multiple due to multiple major versions.
 pub fn find_crates(tcx: TyCtxt<'_>, name: Symbol) -> &'static [DefId] {
     static BY_NAME: OnceLock<FxHashMap<Symbol, Vec<DefId>>> = OnceLock::new();
@@ -274,6 +308,44 @@ fn lookup_with_base(tcx: TyCtxt<'_>, mut base: DefId, ns: PathNS, mut path: &[Sy
     }
 }
 
+/// Returns the names of the items [`item_child_by_name`] and the inherent impls of `def_id` can
+/// resolve.
+fn item_children_names(tcx: TyCtxt<'_>, def_id: DefId) -> Vec<Symbol> {
+    let mut names = Vec::new();
+    match tcx.def_kind(def_id) {
+        DefKind::Mod if let Some(local_id) = def_id.as_local() => {
+            let r#mod = match tcx.hir_node_by_def_id(local_id) {
+                Node::Crate(r#mod) => r#mod,
+                Node::Item(item) if let ItemKind::Mod(_, r#mod) = item.kind => r#mod,
+                _ => return names,
+            };
+            names.extend(
+                r#mod
+                    .item_ids
+                    .iter()
+                    .filter_map(|&item_id| match tcx.hir_item(item_id).kind {
+                        ItemKind::Use(_, UseKind::Single(ident)) => Some(ident.name),
+                        ref kind => kind.ident().map(|ident| ident.name),
+                    }),
+            );
+        },
+        DefKind::Mod | DefKind::Enum => names.extend(tcx.module_children(def_id).iter().map(|child| child.ident.name)),
+        DefKind::Struct | DefKind::Union => names.extend(tcx.adt_def(def_id).all_fields().map(|field| field.name)),
+        DefKind::Trait | DefKind::Impl { .. } => {
+            names.extend(tcx.associated_item_def_ids(def_id).iter().map(|&id| tcx.item_name(id)));
+        },
+        _ => {},
+    }
+    for &impl_def_id in tcx.inherent_impls(def_id) {
+        names.extend(
+            tcx.associated_item_def_ids(impl_def_id)
+                .iter()
+                .map(|&id| tcx.item_name(id)),
+        );
+    }
+    names
+}
+
 fn item_child_by_name(tcx: TyCtxt<'_>, def_id: DefId, ns: PathNS, name: Symbol) -> Option<DefId> {
     if let Some(local_id) = def_id.as_local() {
         local_item_child_by_name(tcx, local_id, ns, name)
@@ -46,6 +46,7 @@ generate! {
     BinaryHeap,
     CLIPPY_ARGS,
     CLIPPY_CONF_DIR,
+    CLIPPY_PROFILE,
     CRLF: "\r\n",
     Cargo_toml: "Cargo.toml",
     Child,
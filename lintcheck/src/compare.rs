@@ -0,0 +1,148 @@
+//! The `compare` subcommand, which runs the Clippys of two revisions on the same crates and
+//! compares their results.
+//!
+//! The base revision is checked out in a Git worktree of the repository, and each Clippy is built
+//! in its own target directory so that switching between them doesn't rebuild everything.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::LintcheckConfig;
+use crate::output::{CrateStats, RustcIce};
+use crate::{
+    build_clippy, clippy_driver_path, json, lint_crates, lint_level_args, prepare_crates, split_entries, target_dir,
+};
+
+/// The results of running one of the Clippys on the crates.
+struct Run {
+    ices: Vec<RustcIce>,
+    /// The stats of each crate, in the order of the crates.
+    stats: Vec<CrateStats>,
+}
+
+pub(crate) fn compare(
+    config: &LintcheckConfig,
+    base: &str,
+    head: Option<&Path>,
+    release: bool,
+    truncate: bool,
+    write_summary: Option<PathBuf>,
+    time_threshold: f64,
+) {
+    if config.fix || config.recursive || config.perf {
+        eprintln!("`lintcheck compare` can't be used with `--fix`, `--recursive` or `--perf`");
+        std::process::exit(1);
+    }
+
+    // Cargo runs in the worktrees, the paths given to it must be absolute
+    let compare_dir = std::path::absolute(format!("{}/lintcheck/compare", target_dir())).unwrap();
+    let base_repo = compare_dir.join("base-repo");
+    checkout_worktree(base, &base_repo);
+
+    println!("Building the base Clippy at {base}");
+    let base_target_dir = compare_dir.join("base-target");
+    build_clippy(release, &base_repo, &base_target_dir);
+    let base_driver = clippy_driver_path(release, &base_target_dir);
+
+    let (head_repo, head_target_dir) = match head {
+        Some(head) => (head, compare_dir.join("head-target")),
+        None => (Path::new("."), std::path::absolute(target_dir()).unwrap()),
+    };
+    println!("Building the head Clippy at {}", head_repo.display());
+    build_clippy(release, head_repo, &head_target_dir);
+    let head_driver = clippy_driver_path(release, &head_target_dir);
+
+    let lint_level_args = lint_level_args(config);
+    let (crates, _) = prepare_crates(config);
+
+    // Both warnings are written next to the usual logs, so that they can be diffed again later
+    let logs_dir = config.lintcheck_results_path.parent().unwrap();
+    let toml_name = config.sources_toml_path.file_stem().unwrap().to_string_lossy();
+    fs::create_dir_all(logs_dir).unwrap();
+    let run = |name: &str, driver: &Path| {
+        println!("Running the {name} Clippy");
+        let clippy_entries = lint_crates(config, &crates, driver, &lint_level_args, None);
+        let (warnings, ices, stats) = split_entries(clippy_entries);
+        let path = logs_dir.join(format!("{toml_name}_{name}.json"));
+        fs::write(&path, json::output(warnings, &stats)).unwrap();
+        (path, Run { ices, stats })
+    };
+    let (base_path, base_run) = run("base", &base_driver);
+    let (head_path, head_run) = run("head", &head_driver);
+
+    json::diff(&base_path, &head_path, truncate, write_summary, time_threshold);
+    print_durations(&base_run, &head_run);
+    if print_new_ices(&base_run, &head_run) {
+        std::process::exit(1);
+    }
+}
+
+/// Checks out `rev` in a worktree of the repository at `dir`, which is reused on the next runs.
+fn checkout_worktree(rev: &str, dir: &Path) {
+    let mut cmd = Command::new("git");
+    if dir.is_dir() {
+        cmd.args(["-c", "advice.detachedHead=false", "checkout", "--detach", rev])
+            .current_dir(dir);
+    } else {
+        cmd.args(["worktree", "add", "--detach"]).arg(dir).arg(rev);
+    }
+    if !cmd.status().expect("failed to run git").success() {
+        eprintln!("Failed to check out {rev} in {}", dir.display());
+        std::process::exit(1);
+    }
+}
+
+/// Prints the check time of every crate, as the diff only lists the regressions.
+fn print_durations(base: &Run, head: &Run) {
+    println!("## Timings");
+    println!();
+    println!("| Crate | Base | Head | Change |");
+    println!("| ----- | ---: | ---: | -----: |");
+    for (base, head) in base.stats.iter().zip(&head.stats) {
+        println!(
+            "| {} {} | {} |",
+            head.name,
+            head.version,
+            format_durations(base.time_ms, head.time_ms)
+        );
+    }
+    let base_total = base.stats.iter().map(|stats| stats.time_ms).sum();
+    let head_total = head.stats.iter().map(|stats| stats.time_ms).sum();
+    println!("| **Total** | {} |", format_durations(base_total, head_total));
+    println!();
+}
+
+fn format_durations(base_ms: f64, head_ms: f64) -> String {
+    let change = (head_ms / base_ms - 1.) * 100.;
+    format!("{:.2}s | {:.2}s | {change:+.1}%", base_ms / 1000., head_ms / 1000.)
+}
+
+/// Prints the ICEs of the head on crates the base didn't ICE on, returning whether there are
+/// some.
+fn print_new_ices(base: &Run, head: &Run) -> bool {
+    let base_ices: HashSet<&str> = base.ices.iter().map(|ice| ice.crate_name.as_str()).collect();
+    let new_ices: Vec<&RustcIce> = head
+        .ices
+        .iter()
+        .filter(|ice| !base_ices.contains(ice.crate_name.as_str()))
+        .collect();
+    if new_ices.is_empty() {
+        return false;
+    }
+
+    println!("## New ICEs");
+    println!();
+    for ice in new_ices {
+        println!("<details><summary><code>{}</code></summary>", ice.crate_name);
+        println!();
+        println!("```");
+        println!("{}", ice.ice_content.trim());
+        println!("```");
+        println!();
+        println!("</details>");
+        println!();
+    }
+    true
+}
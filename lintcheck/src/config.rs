@@ -4,7 +4,6 @@ use std::path::PathBuf;
 
 #[expect(clippy::struct_excessive_bools)]
 #[derive(Parser, Clone, Debug)]
-#[command(args_conflicts_with_subcommands = true)]
 pub(crate) struct LintcheckConfig {
     /// Number of threads to use (default: all unless --fix or --recursive)
     #[clap(
@@ -31,12 +30,20 @@ pub(crate) struct LintcheckConfig {
     /// File to save the clippy lint results here
     #[clap(skip = "")]
     pub lintcheck_results_path: PathBuf, // Overridden in new()
+    /// Read the crates.io sources from the `.crate` files in this directory instead of downloading
+    /// them, e.g. a local registry created by `cargo local-registry`
+    #[clap(long, value_name = "DIR")]
+    pub registry_dir: Option<PathBuf>,
     /// Only process a single crate on the list
     #[clap(long, value_name = "CRATE")]
     pub only: Option<String>,
     /// Runs cargo clippy --fix and checks if all suggestions apply
     #[clap(long, conflicts_with("max_jobs"))]
     pub fix: bool,
+    /// Applies the machine applicable suggestions of each lint on its own after the run and
+    /// reports the ones which caused errors or new warnings
+    #[clap(long, conflicts_with_all(["fix", "recursive", "perf"]))]
+    pub check_fixes: bool,
     /// Apply a filter to only collect specified lints
     #[clap(long = "filter", value_name = "clippy_lint_name", use_value_delimiter = true)]
     pub lint_filter: Vec<String>,
@@ -55,6 +62,10 @@ pub(crate) struct LintcheckConfig {
     /// `target/lintcheck/sources/<package>-<version>/perf.data`
     #[clap(long)]
     pub perf: bool,
+    /// Also record the time spent in the lint passes on each crate in the JSON output, in total
+    /// and for each pass
+    #[clap(long)]
+    pub lint_pass_times: bool,
     #[command(subcommand)]
     pub subcommand: Option<Commands>,
 }
@@ -71,6 +82,49 @@ pub(crate) enum Commands {
         /// Write the diff summary to a JSON file if there are any changes
         #[clap(long, value_name = "PATH")]
         write_summary: Option<PathBuf>,
+        /// Flag the crates whose check time increased by more than this percentage
+        #[clap(long, value_name = "PERCENT", default_value_t = 25.)]
+        time_threshold: f64,
+    },
+    /// Build the Clippy of a Git revision and the Clippy of a worktree, run both on the crates and
+    /// display the markdown diff of their warnings, their timings and the ICEs of the head.
+    /// The options of the runs, like `--filter`, are given before `compare`
+    Compare {
+        /// The Git revision of the base Clippy
+        #[clap(long, value_name = "REV")]
+        base: String,
+        /// The worktree of the head Clippy, the current one by default
+        #[clap(long, value_name = "PATH")]
+        head: Option<PathBuf>,
+        /// Build both Clippys in release mode, for more meaningful timings
+        #[clap(long)]
+        release: bool,
+        /// This will limit the number of warnings that will be printed for each lint
+        #[clap(long)]
+        truncate: bool,
+        /// Write the diff summary to a JSON file if there are any changes
+        #[clap(long, value_name = "PATH")]
+        write_summary: Option<PathBuf>,
+        /// Flag the crates whose check time increased by more than this percentage
+        #[clap(long, value_name = "PERCENT", default_value_t = 25.)]
+        time_threshold: f64,
+    },
+    /// Create a lintcheck crates TOML file from a reproducible random sample of the crates of a
+    /// local crates.io database dump or index checkout
+    Sample {
+        /// The extracted database dump, or the checkout of the index
+        source: PathBuf,
+        /// Output TOML file name
+        output: PathBuf,
+        /// Number of crates to pick
+        #[clap(short, long, default_value_t = 100)]
+        number: usize,
+        /// The seed of the random choice, the same seed and data give the same sample
+        #[clap(long, default_value_t = 0)]
+        seed: u64,
+        /// Pick the same number of crates in each stratum, needs a database dump
+        #[clap(long, value_name = "STRATUM")]
+        by: Option<Stratum>,
     },
     /// Create a lintcheck crates TOML file containing the top N popular crates
     Popular {
@@ -89,6 +143,20 @@ pub(crate) enum OutputFormat {
     Json,
 }
 
+/// How the crates are split up by `lintcheck sample --by`.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Stratum {
+    /// The top level crates.io category, a crate may be in several ones
+    Category,
+    /// The edition of the latest version
+    Edition,
+    /// The tier of the downloads of the last 90 days, by powers of ten
+    Downloads,
+    /// Procedural macro, library or binary only. The procedural macros are recognized by their
+    /// dependency on `proc-macro2`
+    Kind,
+}
+
 impl OutputFormat {
     fn file_extension(self) -> &'static str {
         match self {
@@ -0,0 +1,257 @@
+//! The `--check-fixes` mode, which applies the machine applicable suggestions of each lint on its
+//! own and checks that the crate still compiles without new warnings.
+//!
+//! The suggestions of a lint are applied by `cargo fix --broken-code` to a copy of the sources,
+//! with the `fix_only` field of `CLIPPY_ARGS` set to the lint. The copy is then compiled without
+//! Clippy and its diagnostics are compared to the ones of the unchanged sources.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use cargo_metadata::Message;
+use cargo_metadata::diagnostic::{Diagnostic, DiagnosticLevel};
+use rayon::prelude::*;
+
+use crate::config::LintcheckConfig;
+use crate::input::copy_crate;
+use crate::output::ClippyWarning;
+use crate::{Crate, clippy_args_env_value, lintcheck_sources, shared_target_dir};
+
+/// The number of errors and new warnings printed for each lint and crate.
+const MAX_DIAGNOSTICS: usize = 5;
+
+/// The result of applying the suggestions of a lint to a crate.
+struct LintFixes<'a> {
+    lint: &'a str,
+    krate: &'a Crate,
+    /// The warnings of the lint which have a machine applicable suggestion
+    warnings: Vec<&'a ClippyWarning>,
+    /// The number of suggestions applied by `cargo fix`
+    fixes: usize,
+    errors: Vec<Diagnostic>,
+    /// The warnings which the unchanged sources didn't have
+    new_warnings: Vec<Diagnostic>,
+}
+
+impl LintFixes<'_> {
+    fn is_broken(&self) -> bool {
+        !self.errors.is_empty() || !self.new_warnings.is_empty()
+    }
+}
+
+/// Applies the suggestions of the lints in `warnings` and writes the report next to the logs.
+pub(crate) fn check_fixes(
+    config: &LintcheckConfig,
+    crates: &[Crate],
+    clippy_driver_path: &Path,
+    warnings: &[ClippyWarning],
+) {
+    let results: Vec<LintFixes<'_>> = crates
+        .par_iter()
+        .flat_map(|krate| check_crate(krate, warnings, clippy_driver_path))
+        .collect();
+
+    let text = report(&results);
+    println!("{text}");
+
+    let toml_name = config.sources_toml_path.file_stem().unwrap().to_string_lossy();
+    let path = config
+        .lintcheck_results_path
+        .with_file_name(format!("{toml_name}_fixes.md"));
+    println!("Writing the fixes report to {}", path.display());
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, text).unwrap();
+}
+
+/// Applies the suggestions of each lint that has machine applicable ones in `krate`.
+fn check_crate<'a>(krate: &'a Crate, warnings: &'a [ClippyWarning], clippy_driver_path: &Path) -> Vec<LintFixes<'a>> {
+    let mut lints: BTreeMap<&str, Vec<&ClippyWarning>> = BTreeMap::new();
+    for warning in warnings
+        .iter()
+        .filter(|warning| warning.krate == krate.name && warning.is_machine_applicable())
+    {
+        lints.entry(&warning.name).or_default().push(warning);
+    }
+    if lints.is_empty() {
+        return Vec::new();
+    }
+
+    let target_dir = shared_target_dir(&format!("_fix_{}", rayon::current_thread_index().unwrap_or_default()));
+    // The sources are copied again for each lint, always to the same directory so that the paths
+    // of the diagnostics can be compared
+    let dir = PathBuf::from(format!("{}/{}-{}-fix", lintcheck_sources(), krate.name, krate.version));
+    copy_crate(&krate.path, &dir);
+    let baseline = diagnostics(&dir, &target_dir);
+
+    lints
+        .into_iter()
+        .map(|(lint, warnings)| {
+            println!("Applying the suggestions of {lint} to {} {}", krate.name, krate.version);
+            copy_crate(&krate.path, &dir);
+            let fixes = apply_fixes(krate, lint, &dir, &target_dir, clippy_driver_path);
+            let (errors, new_warnings) = new_diagnostics(&baseline, diagnostics(&dir, &target_dir));
+            LintFixes {
+                lint,
+                krate,
+                warnings,
+                fixes,
+                errors,
+                new_warnings,
+            }
+        })
+        .collect()
+}
+
+/// Applies the machine applicable suggestions of `lint` to the sources in `dir`, keeping them even
+/// if they don't compile, and returns how many were applied.
+fn apply_fixes(krate: &Crate, lint: &str, dir: &Path, target_dir: &Path, clippy_driver_path: &Path) -> usize {
+    let force_warn = format!("--force-warn={lint}");
+    let mut clippy_args: Vec<&str> = krate.options.iter().flatten().map(String::as_str).collect();
+    clippy_args.extend(["--cap-lints=allow", &force_warn]);
+    let fix_only = lint.strip_prefix("clippy::").unwrap_or(lint);
+
+    let output = Command::new("cargo")
+        .args(["fix", "--allow-no-vcs", "--allow-dirty", "--broken-code"])
+        .current_dir(dir)
+        .env("CARGO_TARGET_DIR", target_dir)
+        .env("RUSTC_WORKSPACE_WRAPPER", clippy_driver_path)
+        .env("CLIPPY_ARGS", clippy_args_env_value(&clippy_args, Some(fix_only)))
+        .env("CLIPPY_DISABLE_DOCS_LINKS", "1")
+        .output()
+        .expect("failed to run cargo");
+
+    // `Fixed src/lib.rs (3 fixes)`
+    String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter_map(|line| {
+            let (_, count) = line.trim_start().strip_prefix("Fixed ")?.rsplit_once(" (")?;
+            count.split(' ').next()?.parse::<usize>().ok()
+        })
+        .sum()
+}
+
+/// Compiles all the targets of the sources in `dir` without Clippy, returning their errors and
+/// warnings.
+fn diagnostics(dir: &Path, target_dir: &Path) -> Vec<Diagnostic> {
+    let output = Command::new("cargo")
+        .args(["check", "--all-targets", "--quiet", "--message-format=json"])
+        .current_dir(dir)
+        .env("CARGO_TARGET_DIR", target_dir)
+        .output()
+        .expect("failed to run cargo");
+
+    Message::parse_stream(output.stdout.as_slice())
+        .filter_map(|msg| match msg {
+            // The diagnostics without spans are the summaries, like `aborting due to 2 previous errors`
+            Ok(Message::CompilerMessage(message))
+                if matches!(message.message.level, DiagnosticLevel::Error | DiagnosticLevel::Warning)
+                    && !message.message.spans.is_empty() =>
+            {
+                Some(message.message)
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the errors and the warnings of `diagnostics` which aren't in `baseline`. The lines of
+/// the code change with the suggestions, so the diagnostics are compared by their message.
+fn new_diagnostics(baseline: &[Diagnostic], diagnostics: Vec<Diagnostic>) -> (Vec<Diagnostic>, Vec<Diagnostic>) {
+    let key = |diag: &Diagnostic| {
+        (
+            diag.level,
+            diag.code.as_ref().map(|code| code.code.clone()),
+            diag.message.clone(),
+        )
+    };
+    let mut counts: HashMap<_, usize> = HashMap::new();
+    for diag in baseline {
+        *counts.entry(key(diag)).or_default() += 1;
+    }
+
+    let (mut errors, mut new_warnings) = (Vec::new(), Vec::new());
+    for diag in diagnostics {
+        if let Some(count) = counts.get_mut(&key(&diag))
+            && *count > 0
+        {
+            *count -= 1;
+        } else if diag.level == DiagnosticLevel::Error {
+            errors.push(diag);
+        } else {
+            new_warnings.push(diag);
+        }
+    }
+    (errors, new_warnings)
+}
+
+/// Creates the markdown report, with the totals of each lint followed by the diagnostics of the
+/// broken suggestions.
+fn report(results: &[LintFixes<'_>]) -> String {
+    #[derive(Default)]
+    struct Totals {
+        suggestions: usize,
+        broken: usize,
+        errors: usize,
+        new_warnings: usize,
+    }
+
+    let mut totals: BTreeMap<&str, Totals> = BTreeMap::new();
+    for result in results {
+        let totals = totals.entry(result.lint).or_default();
+        totals.suggestions += result.fixes;
+        if result.is_broken() {
+            totals.broken += result.fixes;
+        }
+        totals.errors += result.errors.len();
+        totals.new_warnings += result.new_warnings.len();
+    }
+
+    let mut text = String::from("## Fixes\n\n");
+    text.push_str(
+        "The suggestions of each lint are applied to each crate together, a suggestion is counted as broken if \
+        the ones of its crate caused errors or new warnings.\n\n",
+    );
+    text.push_str("| Lint | Suggestions | Broken | Errors | New warnings |\n");
+    text.push_str("| ---- | ----------: | -----: | -----: | -----------: |\n");
+    for (lint, totals) in &totals {
+        writeln!(
+            text,
+            "| `{lint}` | {} | {} | {} | {} |",
+            totals.suggestions, totals.broken, totals.errors, totals.new_warnings
+        )
+        .unwrap();
+    }
+
+    let broken: Vec<&LintFixes<'_>> = results.iter().filter(|result| result.is_broken()).collect();
+    if broken.is_empty() {
+        return text;
+    }
+    text.push_str("\n## Broken suggestions\n");
+    for result in broken {
+        writeln!(
+            text,
+            "\n### `{}` on {} {}\n\n{} suggestions caused {} errors and {} new warnings.\n",
+            result.lint,
+            result.krate.name,
+            result.krate.version,
+            result.fixes,
+            result.errors.len(),
+            result.new_warnings.len()
+        )
+        .unwrap();
+        for warning in &result.warnings {
+            let span = warning.span();
+            writeln!(text, "* [`{}:{}`]({})", span.file_name, span.line_start, warning.url).unwrap();
+        }
+        text.push_str("\n```\n");
+        for diag in result.errors.iter().chain(&result.new_warnings).take(MAX_DIAGNOSTICS) {
+            text.push_str(diag.rendered.as_deref().unwrap_or(&diag.message).trim_end());
+            text.push('\n');
+        }
+        text.push_str("```\n");
+    }
+    text
+}
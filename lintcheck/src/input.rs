@@ -36,6 +36,11 @@ struct TomlCrate {
     git_url: Option<String>,
     git_hash: Option<String>,
     path: Option<String>,
+    /// A directory containing the `.crate` file of `version`, used instead of crates.io
+    local_registry: Option<String>,
+    /// A directory created by `cargo vendor` containing the sources of `version`, used instead of
+    /// crates.io
+    vendor: Option<String>,
     options: Option<Vec<String>>,
     /// Magic values:
     /// * `{krate}` will be replaced by `self.name`
@@ -84,15 +89,23 @@ pub struct CrateWithSource {
     pub options: Option<Vec<String>>,
 }
 
+/// Where to get the sources of a crate from. `LocalRegistry` reads a crates.io crate from the
+/// `.crate` file in `dir` instead of downloading it, and `Vendor` copies a crate already extracted
+/// by `cargo vendor` in `dir`.
 #[derive(Debug, Deserialize, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub enum CrateSource {
     CratesIo { version: String },
+    LocalRegistry { dir: PathBuf, version: String },
+    Vendor { dir: PathBuf, version: String },
     Git { url: String, commit: String },
     Path { path: PathBuf },
 }
 
 /// Read a `lintcheck_crates.toml` file
-pub fn read_crates(toml_path: &Path) -> (Vec<CrateWithSource>, RecursiveOptions) {
+///
+/// If `registry_dir` is set, the crates.io sources are read from the `.crate` files it contains
+/// instead of being downloaded.
+pub fn read_crates(toml_path: &Path, registry_dir: Option<&Path>) -> (Vec<CrateWithSource>, RecursiveOptions) {
     let toml_content: String =
         fs::read_to_string(toml_path).unwrap_or_else(|_| panic!("Failed to read {}", toml_path.display()));
     let crate_list: SourceList =
@@ -114,11 +127,25 @@ pub fn read_crates(toml_path: &Path) -> (Vec<CrateWithSource>, RecursiveOptions)
                 options: tk.options.clone(),
             });
         } else if let Some(ref version) = tk.version {
+            let local_registry = tk.local_registry.as_deref().map(Path::new).or(registry_dir);
+            let source = if let Some(vendor) = &tk.vendor {
+                CrateSource::Vendor {
+                    dir: PathBuf::from(vendor),
+                    version: version.clone(),
+                }
+            } else if let Some(dir) = local_registry {
+                CrateSource::LocalRegistry {
+                    dir: dir.to_path_buf(),
+                    version: version.clone(),
+                }
+            } else {
+                CrateSource::CratesIo {
+                    version: version.clone(),
+                }
+            };
             crate_sources.push(CrateWithSource {
                 name: tk.name.clone(),
-                source: CrateSource::CratesIo {
-                    version: version.clone(),
-                },
+                source,
                 file_link: tk.file_link(DEFAULT_DOCS_LINK),
                 options: tk.options.clone(),
             });
@@ -151,6 +178,10 @@ pub fn read_crates(toml_path: &Path) -> (Vec<CrateWithSource>, RecursiveOptions)
                 tk.path.is_none() || (tk.git_hash.is_none() && tk.version.is_none()),
                 "Error: TomlCrate can only have one of 'git_.*', 'version' or 'path' fields"
             );
+            assert!(
+                tk.vendor.is_none() && tk.local_registry.is_none(),
+                "Error: TomlCrate can't have 'git_.*' fields with 'vendor' or 'local_registry'"
+            );
             unreachable!("Failed to translate TomlCrate into CrateSource!");
         }
     }
@@ -220,11 +251,7 @@ impl CrateWithSource {
                     // copy the crate into the file
                     io::copy(&mut krate_req, &mut krate_dest).unwrap();
 
-                    // unzip the tarball
-                    let ungz_tar = flate2::read::GzDecoder::new(fs::File::open(&krate_file_path).unwrap());
-                    // extract the tar archive
-                    let mut archive = tar::Archive::new(ungz_tar);
-                    archive.unpack(&extract_dir).expect("Failed to extract!");
+                    extract_crate(&krate_file_path, &extract_dir);
                 }
                 // crate is extracted, return a new Krate object which contains the path to the extracted
                 // sources that clippy can check
@@ -236,6 +263,56 @@ impl CrateWithSource {
                     base_url: file_link.clone(),
                 }
             },
+            CrateSource::LocalRegistry { dir, version } => {
+                let extract_dir = PathBuf::from(lintcheck_sources());
+                fs::create_dir_all(&extract_dir).unwrap();
+
+                // `cargo local-registry` stores the crates at the root of the registry, a mirror of
+                // static.crates.io in a directory per crate
+                let file_name = format!("{name}-{version}.crate");
+                let krate_file_path = [dir.join(&file_name), dir.join(name).join(&file_name)]
+                    .into_iter()
+                    .find(|path| path.is_file())
+                    .unwrap_or_else(|| panic!("Failed to find `{file_name}` in the registry at {}", dir.display()));
+
+                if !extract_dir.join(format!("{name}-{version}")).exists() {
+                    println!("Extracting {name} {version} from {}", krate_file_path.display());
+                    extract_crate(&krate_file_path, &extract_dir);
+                }
+                Crate {
+                    version: version.clone(),
+                    name: name.clone(),
+                    path: extract_dir.join(format!("{name}-{version}/")),
+                    options: options.clone(),
+                    base_url: file_link.clone(),
+                }
+            },
+            CrateSource::Vendor { dir, version } => {
+                // `cargo vendor` only adds the version to the directory of a crate vendored in
+                // several versions, or when given `--versioned-dirs`
+                let mut vendored = dir.join(format!("{name}-{version}"));
+                if !vendored.is_dir() {
+                    vendored = dir.join(name);
+                }
+                let vendored_version = read_package_version(&vendored);
+                assert_eq!(
+                    *version,
+                    vendored_version,
+                    "{name} is vendored in version {vendored_version} at {}",
+                    vendored.display()
+                );
+
+                let dest_crate_root = PathBuf::from(lintcheck_sources()).join(format!("{name}-{version}-vendor"));
+                copy_crate(&vendored, &dest_crate_root);
+
+                Crate {
+                    version: version.clone(),
+                    name: name.clone(),
+                    path: dest_crate_root,
+                    options: options.clone(),
+                    base_url: file_link.clone(),
+                }
+            },
             CrateSource::Git { url, commit } => {
                 let repo_path = {
                     let mut repo_path = PathBuf::from(lintcheck_sources());
@@ -279,35 +356,8 @@ impl CrateWithSource {
                 }
             },
             CrateSource::Path { path } => {
-                fn is_cache_dir(entry: &DirEntry) -> bool {
-                    fs::read(entry.path().join("CACHEDIR.TAG"))
-                        .is_ok_and(|x| x.starts_with(b"Signature: 8a477f597d28d172789f06886806bc55"))
-                }
-
-                // copy path into the dest_crate_root but skip directories that contain a CACHEDIR.TAG file.
-                // The target/ directory contains a CACHEDIR.TAG file so it is the most commonly skipped directory
-                // as a result of this filter.
                 let dest_crate_root = PathBuf::from(lintcheck_sources()).join(name);
-                if dest_crate_root.exists() {
-                    println!("Deleting existing directory at `{}`", dest_crate_root.display());
-                    fs::remove_dir_all(&dest_crate_root).unwrap();
-                }
-
-                println!("Copying `{}` to `{}`", path.display(), dest_crate_root.display());
-
-                for entry in WalkDir::new(path).into_iter().filter_entry(|e| !is_cache_dir(e)) {
-                    let entry = entry.unwrap();
-                    let entry_path = entry.path();
-                    let relative_entry_path = entry_path.strip_prefix(path).unwrap();
-                    let dest_path = dest_crate_root.join(relative_entry_path);
-                    let metadata = entry_path.symlink_metadata().unwrap();
-
-                    if metadata.is_dir() {
-                        fs::create_dir(dest_path).unwrap();
-                    } else if metadata.is_file() {
-                        fs::copy(entry_path, dest_path).unwrap();
-                    }
-                }
+                copy_crate(path, &dest_crate_root);
 
                 Crate {
                     version: String::from("local"),
@@ -321,6 +371,62 @@ impl CrateWithSource {
     }
 }
 
+/// Extracts the `.crate` tarball at `krate_file_path` into `extract_dir`.
+fn extract_crate(krate_file_path: &Path, extract_dir: &Path) {
+    // unzip the tarball
+    let ungz_tar = flate2::read::GzDecoder::new(fs::File::open(krate_file_path).unwrap());
+    // extract the tar archive
+    let mut archive = tar::Archive::new(ungz_tar);
+    archive.unpack(extract_dir).expect("Failed to extract!");
+}
+
+/// Copies the crate at `path` into `dest_crate_root`, replacing it if it exists.
+pub(crate) fn copy_crate(path: &Path, dest_crate_root: &Path) {
+    fn is_cache_dir(entry: &DirEntry) -> bool {
+        fs::read(entry.path().join("CACHEDIR.TAG"))
+            .is_ok_and(|x| x.starts_with(b"Signature: 8a477f597d28d172789f06886806bc55"))
+    }
+
+    // copy path into the dest_crate_root but skip directories that contain a CACHEDIR.TAG file.
+    // The target/ directory contains a CACHEDIR.TAG file so it is the most commonly skipped directory
+    // as a result of this filter.
+    if dest_crate_root.exists() {
+        println!("Deleting existing directory at `{}`", dest_crate_root.display());
+        fs::remove_dir_all(dest_crate_root).unwrap();
+    }
+
+    println!("Copying `{}` to `{}`", path.display(), dest_crate_root.display());
+
+    for entry in WalkDir::new(path).into_iter().filter_entry(|e| !is_cache_dir(e)) {
+        let entry = entry.unwrap();
+        let entry_path = entry.path();
+        let relative_entry_path = entry_path.strip_prefix(path).unwrap();
+        let dest_path = dest_crate_root.join(relative_entry_path);
+        let metadata = entry_path.symlink_metadata().unwrap();
+
+        if metadata.is_dir() {
+            fs::create_dir_all(dest_path).unwrap();
+        } else if metadata.is_file() {
+            fs::copy(entry_path, dest_path).unwrap();
+        }
+    }
+}
+
+/// Reads the version of the package whose `Cargo.toml` is in `dir`.
+fn read_package_version(dir: &Path) -> String {
+    let manifest_path = dir.join("Cargo.toml");
+    let manifest: toml::Table = fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|manifest| toml::from_str(&manifest).ok())
+        .unwrap_or_else(|| panic!("Failed to read {}", manifest_path.display()));
+    manifest
+        .get("package")
+        .and_then(|package| package.get("version"))
+        .and_then(|version| version.as_str())
+        .unwrap_or_else(|| panic!("Missing package version in {}", manifest_path.display()))
+        .to_string()
+}
+
 /// Create necessary directories to run the lintcheck tool.
 ///
 /// # Panics
@@ -4,6 +4,7 @@
 //! loading warnings from JSON files, and generating human-readable diffs
 //! between different linting runs.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::{fmt, fs};
 
@@ -11,11 +12,31 @@ use itertools::{EitherOrBoth, Itertools};
 use serde::{Deserialize, Serialize};
 
 use crate::ClippyWarning;
+use crate::output::CrateStats;
 
 /// This is the total number. 300 warnings results in 100 messages per section.
 const DEFAULT_LIMIT_PER_LINT: usize = 300;
 /// Target for total warnings to display across all lints when truncating output.
 const TRUNCATION_TOTAL_TARGET: usize = 1000;
+/// Check time increases below this many milliseconds are considered noise.
+const MIN_TIME_REGRESSION_MS: f64 = 500.;
+/// The number of lint passes listed for a crate whose check time regressed.
+const SLOWER_PASSES_LIMIT: usize = 5;
+
+/// The contents of the JSON log file.
+#[derive(Debug, Deserialize, Serialize)]
+struct LogJson {
+    warnings: Vec<LintJson>,
+    crates: Vec<CrateStats>,
+}
+
+/// The logs written by lintcheck before the crate stats were recorded only contained the warnings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnyLogJson {
+    Log(LogJson),
+    Warnings(Vec<LintJson>),
+}
 
 #[derive(Debug, Deserialize, Serialize)]
 struct LintJson {
@@ -90,7 +111,14 @@ impl Summary {
 }
 
 /// Creates the log file output for [`crate::config::OutputFormat::Json`]
-pub(crate) fn output(clippy_warnings: Vec<ClippyWarning>) -> String {
+pub(crate) fn output(clippy_warnings: Vec<ClippyWarning>, crates: &[CrateStats]) -> String {
+    /// [`LogJson`] with borrowed crate stats
+    #[derive(Serialize)]
+    struct Log<'a> {
+        warnings: Vec<LintJson>,
+        crates: &'a [CrateStats],
+    }
+
     let mut lints: Vec<LintJson> = clippy_warnings
         .into_iter()
         .map(|warning| {
@@ -109,23 +137,42 @@ pub(crate) fn output(clippy_warnings: Vec<ClippyWarning>) -> String {
         })
         .collect();
     lints.sort_by(|a, b| a.key().cmp(&b.key()));
-    serde_json::to_string(&lints).unwrap()
+    serde_json::to_string(&Log {
+        warnings: lints,
+        crates,
+    })
+    .unwrap()
 }
 
-/// Loads lint warnings from a JSON file at the given path.
-fn load_warnings(path: &Path) -> Vec<LintJson> {
+/// Loads lint warnings and crate stats from a JSON file at the given path.
+fn load_log(path: &Path) -> LogJson {
     let file = fs::read(path).unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
 
-    serde_json::from_slice(&file).unwrap_or_else(|e| panic!("failed to deserialize {}: {e}", path.display()))
+    match serde_json::from_slice(&file).unwrap_or_else(|e| panic!("failed to deserialize {}: {e}", path.display())) {
+        AnyLogJson::Log(log) => log,
+        AnyLogJson::Warnings(warnings) => LogJson {
+            warnings,
+            crates: Vec::new(),
+        },
+    }
 }
 
 /// Generates and prints a diff between two sets of lint warnings.
 ///
 /// Compares warnings from `old_path` and `new_path`, then displays a summary table
-/// and detailed information about added, removed, and changed warnings.
-pub(crate) fn diff(old_path: &Path, new_path: &Path, truncate: bool, write_summary: Option<PathBuf>) {
-    let old_warnings = load_warnings(old_path);
-    let new_warnings = load_warnings(new_path);
+/// and detailed information about added, removed, and changed warnings, followed by the crates
+/// whose check time increased by more than `time_threshold` percent.
+pub(crate) fn diff(
+    old_path: &Path,
+    new_path: &Path,
+    truncate: bool,
+    write_summary: Option<PathBuf>,
+    time_threshold: f64,
+) {
+    let old_log = load_log(old_path);
+    let new_log = load_log(new_path);
+    let old_warnings = old_log.warnings;
+    let new_warnings = new_log.warnings;
 
     let mut lint_warnings = vec![];
 
@@ -157,7 +204,10 @@ pub(crate) fn diff(old_path: &Path, new_path: &Path, truncate: bool, write_summa
         }
     }
 
+    let regressions = time_regressions(&old_log.crates, &new_log.crates, time_threshold);
+
     if lint_warnings.is_empty() {
+        print_time_regressions(&regressions, time_threshold);
         return;
     }
 
@@ -179,11 +229,87 @@ pub(crate) fn diff(old_path: &Path, new_path: &Path, truncate: bool, write_summa
     };
 
     println!("{summary}");
+    print_time_regressions(&regressions, time_threshold);
     for lint in lint_warnings {
         print_lint_warnings(&lint, truncate_after);
     }
 }
 
+/// Returns the crates of `new` whose check time increased by more than `threshold` percent since
+/// `old`, along with their old stats.
+fn time_regressions<'a>(
+    old: &'a [CrateStats],
+    new: &'a [CrateStats],
+    threshold: f64,
+) -> Vec<(&'a CrateStats, &'a CrateStats)> {
+    let old: HashMap<_, _> = old
+        .iter()
+        .map(|stats| ((stats.name.as_str(), stats.version.as_str()), stats))
+        .collect();
+    new.iter()
+        .filter_map(|new| {
+            let old = old.get(&(new.name.as_str(), new.version.as_str()))?;
+            (new.time_ms > old.time_ms * (1. + threshold / 100.) && new.time_ms - old.time_ms >= MIN_TIME_REGRESSION_MS)
+                .then_some((*old, new))
+        })
+        .collect()
+}
+
+fn print_time_regressions(regressions: &[(&CrateStats, &CrateStats)], threshold: f64) {
+    if regressions.is_empty() {
+        return;
+    }
+
+    println!("## Check time regressions");
+    println!();
+    println!("The check time of these crates increased by more than {threshold}%:");
+    println!();
+    println!("| Crate | Check time | Lint passes | Peak RSS |");
+    println!("| ----- | ---------: | ----------: | -------: |");
+    for (old, new) in regressions {
+        let rss = match (old.max_rss_kb, new.max_rss_kb) {
+            (Some(old), Some(new)) => format!("{} MiB → {} MiB", old / 1024, new / 1024),
+            _ => "-".to_string(),
+        };
+        let lint_time = match (old.lint_time_ms, new.lint_time_ms) {
+            (Some(old), Some(new)) => format!("{:.2}s → {:.2}s", old / 1000., new / 1000.),
+            _ => "-".to_string(),
+        };
+        println!(
+            "| {} {} | {:.2}s → {:.2}s ({:+.1}%) | {lint_time} | {rss} |",
+            new.name,
+            new.version,
+            old.time_ms / 1000.,
+            new.time_ms / 1000.,
+            (new.time_ms / old.time_ms - 1.) * 100.,
+        );
+    }
+    println!();
+
+    // The lint passes are only recorded with `--lint-pass-times`
+    for (old, new) in regressions {
+        let mut slower_passes: Vec<(&str, f64, f64)> = new
+            .lint_passes
+            .iter()
+            .filter_map(|(name, &new_ms)| {
+                let old_ms = old.lint_passes.get(name).copied().unwrap_or_default();
+                (new_ms > old_ms).then_some((name.as_str(), old_ms, new_ms))
+            })
+            .collect();
+        if slower_passes.is_empty() {
+            continue;
+        }
+        slower_passes.sort_by(|(_, a_old, a_new), (_, b_old, b_new)| (b_new - b_old).total_cmp(&(a_new - a_old)));
+
+        println!("Slower lint passes on `{} {}`:", new.name, new.version);
+        println!();
+        for (name, old_ms, new_ms) in slower_passes.into_iter().take(SLOWER_PASSES_LIMIT) {
+            println!("* `{name}`: {old_ms:.1}ms → {new_ms:.1}ms");
+        }
+        println!();
+    }
+}
+
 /// Container for grouped lint warnings organized by status (added/removed/changed).
 #[derive(Debug)]
 struct LintWarnings {
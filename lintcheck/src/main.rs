@@ -20,13 +20,17 @@
     clippy::literal_string_with_formatting_args
 )]
 
+mod compare;
 mod config;
 mod driver;
+mod fix_check;
 mod input;
 mod json;
 mod output;
 mod popular_crates;
 mod recursive;
+mod resources;
+mod sample;
 
 use crate::config::{Commands, LintcheckConfig, OutputFormat};
 use crate::recursive::LintcheckServer;
@@ -36,11 +40,12 @@ use std::io::{self};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use std::{env, fs};
 
 use cargo_metadata::Message;
-use input::read_crates;
-use output::{ClippyCheckOutput, ClippyWarning, RustcIce};
+use input::{RecursiveOptions, read_crates};
+use output::{ClippyCheckOutput, ClippyWarning, CrateStats, RustcIce};
 use rayon::prelude::*;
 
 #[must_use]
@@ -146,7 +151,7 @@ impl Crate {
         cmd.arg(if config.fix { "fix" } else { "check" })
             .arg("--quiet")
             .current_dir(&self.path)
-            .env("CLIPPY_ARGS", clippy_args.join("__CLIPPY_HACKERY__"))
+            .env("CLIPPY_ARGS", clippy_args_env_value(&clippy_args, None))
             .env("CLIPPY_DISABLE_DOCS_LINKS", "1");
 
         if let Some(server) = server {
@@ -178,13 +183,35 @@ impl Crate {
         }
 
         let shared_target_dir = shared_target_dir(&format!("_{thread_index:?}"));
-        let all_output = cmd
-            // use the looping index to create individual target dirs
-            .env("CARGO_TARGET_DIR", shared_target_dir.as_os_str())
-            // Roughly equivalent to `cargo clippy`/`cargo clippy --fix`
-            .env("RUSTC_WORKSPACE_WRAPPER", clippy_driver_path)
-            .output()
-            .unwrap();
+        // Makes Cargo lint the crate again instead of replaying the warnings of the previous run,
+        // which would make the measures meaningless. `clippy-driver` tracks the `Cargo.toml`.
+        let _ = fs::File::options()
+            .write(true)
+            .open(self.path.join("Cargo.toml"))
+            .and_then(|manifest| manifest.set_modified(SystemTime::now()));
+        // `clippy-driver` appends the lint pass times to this file
+        let lint_pass_times = config.lint_pass_times.then(|| {
+            let nonce = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+            shared_target_dir.join(format!(
+                "lint-pass-times-{}-{}-{}.jsonl",
+                self.name,
+                self.version,
+                nonce.as_nanos()
+            ))
+        });
+        if let Some(lint_pass_times) = &lint_pass_times {
+            cmd.env("CLIPPY_PROFILE", format!("json:{}", lint_pass_times.display()));
+        }
+        let start = Instant::now();
+        let (all_output, max_rss_kb) = resources::output_with_max_rss(
+            cmd
+                // use the looping index to create individual target dirs
+                .env("CARGO_TARGET_DIR", shared_target_dir.as_os_str())
+                // Roughly equivalent to `cargo clippy`/`cargo clippy --fix`
+                .env("RUSTC_WORKSPACE_WRAPPER", clippy_driver_path),
+        )
+        .unwrap();
+        let crate_stats = CrateStats::new(self, start.elapsed(), max_rss_kb, lint_pass_times.as_deref());
         let stdout = String::from_utf8_lossy(&all_output.stdout);
         let stderr = String::from_utf8_lossy(&all_output.stderr);
         let status = &all_output.status;
@@ -234,11 +261,26 @@ impl Crate {
         } else if !status.success() {
             println!("non-ICE bad exit status for {} {}: {}", self.name, self.version, stderr);
         }
+        entries.push(ClippyCheckOutput::CrateStats(crate_stats));
 
         entries
     }
 }
 
+/// Serializes the arguments of `clippy-driver` like `cargo clippy` does, as the version 1 of the
+/// `CLIPPY_ARGS` format, passing them all to the compiler. With `fix_only`, `cargo fix` only
+/// applies the suggestions of that lint, like `cargo clippy --fix --fix-only`.
+fn clippy_args_env_value(clippy_args: &[&str], fix_only: Option<&str>) -> String {
+    let mut value = serde_json::json!({
+        "version": 1,
+        "rustc_args": clippy_args,
+    });
+    if let Some(lint) = fix_only {
+        value["fix_only"] = serde_json::json!([lint]);
+    }
+    value.to_string()
+}
+
 /// The target directory can sometimes be stored in the file name of spans.
 /// This is problematic since the directory in constructed from the thread
 /// ID and also used in our CI to determine if two lint emissions are the
@@ -264,8 +306,9 @@ fn normalize_diag(
     message
 }
 
-/// Builds clippy inside the repo to make sure we have a clippy executable we can use.
-fn build_clippy(release_build: bool) -> String {
+/// Builds the clippy of the repo at `repo` in `target_dir` to make sure we have a clippy executable
+/// we can use.
+fn build_clippy(release_build: bool, repo: &Path, target_dir: &Path) -> String {
     let mut build_cmd = Command::new("cargo");
     build_cmd.args([
         "run",
@@ -279,7 +322,12 @@ fn build_clippy(release_build: bool) -> String {
         build_cmd.env("CARGO_PROFILE_RELEASE_DEBUG", "true");
     }
 
-    let output = build_cmd.stderr(Stdio::inherit()).output().unwrap();
+    let output = build_cmd
+        .current_dir(repo)
+        .env("CARGO_TARGET_DIR", target_dir)
+        .stderr(Stdio::inherit())
+        .output()
+        .unwrap();
 
     if !output.status.success() {
         eprintln!("Error: Failed to compile Clippy!");
@@ -288,6 +336,17 @@ fn build_clippy(release_build: bool) -> String {
     String::from_utf8_lossy(&output.stdout).into_owned()
 }
 
+/// Returns the absolute path of the `clippy-driver` built by [`build_clippy`] in `target_dir`.
+fn clippy_driver_path(release_build: bool, target_dir: &Path) -> PathBuf {
+    let path = target_dir
+        .join(if release_build { "release" } else { "debug" })
+        .join(format!("clippy-driver{EXE_SUFFIX}"));
+
+    // assert that clippy is found
+    assert!(path.is_file(), "{} binary not found!", path.display());
+    fs::canonicalize(path).unwrap()
+}
+
 fn main() {
     // We're being executed as a `RUSTC_WRAPPER` as part of `--recursive`
     if let Ok(addr) = env::var("LINTCHECK_SERVER") {
@@ -308,37 +367,38 @@ fn main() {
             new,
             truncate,
             write_summary,
-        }) => json::diff(&old, &new, truncate, write_summary),
+            time_threshold,
+        }) => json::diff(&old, &new, truncate, write_summary, time_threshold),
         Some(Commands::Popular { output, number }) => popular_crates::fetch(output, number).unwrap(),
-        None => lintcheck(config),
+        Some(Commands::Sample {
+            ref source,
+            ref output,
+            number,
+            seed,
+            by,
+        }) => sample::sample(source, output, number, seed, by).unwrap(),
+        Some(Commands::Compare {
+            ref base,
+            ref head,
+            release,
+            truncate,
+            ref write_summary,
+            time_threshold,
+        }) => compare::compare(
+            &config,
+            base,
+            head.as_deref(),
+            release,
+            truncate,
+            write_summary.clone(),
+            time_threshold,
+        ),
+        None => lintcheck(&config),
     }
 }
 
-#[expect(clippy::too_many_lines)]
-fn lintcheck(config: LintcheckConfig) {
-    let clippy_ver = build_clippy(config.perf);
-    let clippy_driver_path = fs::canonicalize(format!(
-        "{}/{}/clippy-driver{EXE_SUFFIX}",
-        target_dir(),
-        if config.perf { "release" } else { "debug" }
-    ))
-    .unwrap();
-
-    // assert that clippy is found
-    assert!(
-        clippy_driver_path.is_file(),
-        "{}/{}/clippy-driver binary not found! {}",
-        target_dir(),
-        if config.perf { "release" } else { "debug" },
-        clippy_driver_path.display()
-    );
-
-    // download and extract the crates, then run clippy on them and collect clippy's warnings
-    // flatten into one big list of warnings
-
-    let (crates, recursive_options) = read_crates(&config.sources_toml_path);
-
-    let counter = AtomicUsize::new(1);
+/// Returns the lint level arguments that select the lints to check.
+fn lint_level_args(config: &LintcheckConfig) -> Vec<String> {
     let mut lint_level_args: Vec<String> = vec!["--cap-lints=allow".into()];
     if config.lint_filter.is_empty() {
         let groups = if config.all_lints {
@@ -367,6 +427,12 @@ fn lintcheck(config: LintcheckConfig) {
             })
             .collect_into(&mut lint_level_args);
     }
+    lint_level_args
+}
+
+/// Downloads and extracts the crates to check, and sets up the thread pool to check them.
+fn prepare_crates(config: &LintcheckConfig) -> (Vec<Crate>, RecursiveOptions) {
+    let (crates, recursive_options) = read_crates(&config.sources_toml_path, config.registry_dir.as_deref());
 
     let crates: Vec<Crate> = crates
         .into_iter()
@@ -383,7 +449,7 @@ fn lintcheck(config: LintcheckConfig) {
     if crates.is_empty() {
         eprintln!(
             "ERROR: could not find crate '{}' in lintcheck/lintcheck_crates.toml",
-            config.only.unwrap(),
+            config.only.as_deref().unwrap(),
         );
         std::process::exit(1);
     }
@@ -398,25 +464,65 @@ fn lintcheck(config: LintcheckConfig) {
         .build_global()
         .unwrap();
 
-    let server = config.recursive.then(|| {
-        let _: io::Result<()> = fs::remove_dir_all(format!("{}/lintcheck/shared_target_dir/recursive", target_dir()));
-
-        LintcheckServer::spawn(recursive_options)
-    });
+    (crates, recursive_options)
+}
 
-    let mut clippy_entries: Vec<ClippyCheckOutput> = crates
+/// Runs the `clippy-driver` at `clippy_driver_path` on the crates.
+fn lint_crates(
+    config: &LintcheckConfig,
+    crates: &[Crate],
+    clippy_driver_path: &Path,
+    lint_level_args: &[String],
+    server: Option<&LintcheckServer>,
+) -> Vec<ClippyCheckOutput> {
+    let counter = AtomicUsize::new(1);
+    crates
         .par_iter()
         .flat_map(|krate| {
             krate.run_clippy_lints(
-                &clippy_driver_path,
+                clippy_driver_path,
                 &counter,
                 crates.len(),
-                &config,
-                &lint_level_args,
-                server.as_ref(),
+                config,
+                lint_level_args,
+                server,
             )
         })
-        .collect();
+        .collect()
+}
+
+/// Splits up the warnings, the ICEs and the stats of the crates.
+fn split_entries(clippy_entries: Vec<ClippyCheckOutput>) -> (Vec<ClippyWarning>, Vec<RustcIce>, Vec<CrateStats>) {
+    let mut warnings: Vec<ClippyWarning> = vec![];
+    let mut raw_ices: Vec<RustcIce> = vec![];
+    let mut stats: Vec<CrateStats> = vec![];
+    for entry in clippy_entries {
+        match entry {
+            ClippyCheckOutput::ClippyWarning(x) => warnings.push(x),
+            ClippyCheckOutput::RustcIce(x) => raw_ices.push(x),
+            ClippyCheckOutput::CrateStats(x) => stats.push(x),
+        }
+    }
+    (warnings, raw_ices, stats)
+}
+
+fn lintcheck(config: &LintcheckConfig) {
+    let clippy_ver = build_clippy(config.perf, Path::new("."), Path::new(&target_dir()));
+    let clippy_driver_path = clippy_driver_path(config.perf, Path::new(&target_dir()));
+
+    // download and extract the crates, then run clippy on them and collect clippy's warnings
+    // flatten into one big list of warnings
+
+    let lint_level_args = lint_level_args(config);
+    let (crates, recursive_options) = prepare_crates(config);
+
+    let server = config.recursive.then(|| {
+        let _: io::Result<()> = fs::remove_dir_all(format!("{}/lintcheck/shared_target_dir/recursive", target_dir()));
+
+        LintcheckServer::spawn(recursive_options)
+    });
+
+    let mut clippy_entries = lint_crates(config, &crates, &clippy_driver_path, &lint_level_args, server.as_ref());
 
     if let Some(server) = server {
         let server_clippy_entries = server.warnings().map(ClippyCheckOutput::ClippyWarning);
@@ -429,20 +535,15 @@ fn lintcheck(config: LintcheckConfig) {
         return;
     }
 
-    // split up warnings and ices
-    let mut warnings: Vec<ClippyWarning> = vec![];
-    let mut raw_ices: Vec<RustcIce> = vec![];
-    for entry in clippy_entries {
-        if let ClippyCheckOutput::ClippyWarning(x) = entry {
-            warnings.push(x);
-        } else if let ClippyCheckOutput::RustcIce(x) = entry {
-            raw_ices.push(x);
-        }
+    let (warnings, raw_ices, stats) = split_entries(clippy_entries);
+
+    if config.check_fixes {
+        fix_check::check_fixes(config, &crates, &clippy_driver_path, &warnings);
     }
 
     let text = match config.format {
         OutputFormat::Text | OutputFormat::Markdown => {
-            output::summarize_and_print_changes(&warnings, &raw_ices, clippy_ver, &config)
+            output::summarize_and_print_changes(&warnings, &raw_ices, clippy_ver, config)
         },
         OutputFormat::Json => {
             if !raw_ices.is_empty() {
@@ -452,7 +553,7 @@ fn lintcheck(config: LintcheckConfig) {
                 panic!("Some crates ICEd");
             }
 
-            json::output(warnings)
+            json::output(warnings, &stats)
         },
     };
 
@@ -1,20 +1,87 @@
-use cargo_metadata::diagnostic::{Diagnostic, DiagnosticSpan};
+use cargo_metadata::diagnostic::{Applicability, Diagnostic, DiagnosticSpan};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{self, Write as _};
 use std::fs;
 use std::path::Path;
 use std::process::ExitStatus;
+use std::time::Duration;
 
+use crate::Crate;
 use crate::config::{LintcheckConfig, OutputFormat};
 
 /// A single emitted output from clippy being executed on a crate. It may either be a
-/// `ClippyWarning`, or a `RustcIce` caused by a panic within clippy. A crate may have many
-/// `ClippyWarning`s but a maximum of one `RustcIce` (at which point clippy halts execution).
+/// `ClippyWarning`, a `RustcIce` caused by a panic within clippy, or the `CrateStats` of the run. A
+/// crate may have many `ClippyWarning`s but a maximum of one `RustcIce` (at which point clippy
+/// halts execution).
 #[derive(Debug)]
 pub enum ClippyCheckOutput {
     ClippyWarning(ClippyWarning),
     RustcIce(RustcIce),
+    CrateStats(CrateStats),
+}
+
+/// The resources used to check a crate
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrateStats {
+    pub name: String,
+    pub version: String,
+    /// The wall time of `cargo check`, in milliseconds
+    pub time_ms: f64,
+    /// The time spent in the lint passes, in milliseconds, recorded with `--lint-pass-times`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lint_time_ms: Option<f64>,
+    /// The peak resident set size of `cargo check` and of the compilers it ran, in kilobytes, on
+    /// the platforms where it's measured
+    pub max_rss_kb: Option<u64>,
+    /// The time of each lint pass in milliseconds, recorded with `--lint-pass-times`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lint_passes: BTreeMap<String, f64>,
+}
+
+impl CrateStats {
+    /// Reads the lint pass times written by `clippy-driver` to `lint_pass_times` if given, the
+    /// value of `CLIPPY_PROFILE` being `json:<lint_pass_times>`.
+    pub fn new(krate: &Crate, time: Duration, max_rss_kb: Option<u64>, lint_pass_times: Option<&Path>) -> Self {
+        #[derive(Deserialize)]
+        struct Profile {
+            total_ms: f64,
+            passes: Vec<Pass>,
+        }
+        #[derive(Deserialize)]
+        struct Pass {
+            name: String,
+            time_ms: f64,
+        }
+
+        let mut lint_time_ms = None;
+        let mut lint_passes = BTreeMap::new();
+        if let Some(lint_pass_times) = lint_pass_times {
+            // A line per linted target of the crate, there is no file if none was linted
+            let profiles = fs::read_to_string(lint_pass_times).unwrap_or_default();
+            let _ = fs::remove_file(lint_pass_times);
+            let mut total_ms = 0.;
+            for profile in profiles
+                .lines()
+                .filter_map(|line| serde_json::from_str::<Profile>(line).ok())
+            {
+                total_ms += profile.total_ms;
+                for pass in profile.passes {
+                    *lint_passes.entry(pass.name).or_default() += pass.time_ms;
+                }
+            }
+            lint_time_ms = Some(total_ms);
+        }
+
+        Self {
+            name: krate.name.clone(),
+            version: krate.version.clone(),
+            time_ms: time.as_secs_f64() * 1000.,
+            lint_time_ms,
+            max_rss_kb,
+            lint_passes,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -103,6 +170,15 @@ impl ClippyWarning {
         self.diag.spans.iter().find(|span| span.is_primary).unwrap()
     }
 
+    /// Whether `cargo fix` applies a suggestion of the warning
+    pub fn is_machine_applicable(&self) -> bool {
+        self.diag
+            .spans
+            .iter()
+            .chain(self.diag.children.iter().flat_map(|child| &child.spans))
+            .any(|span| span.suggestion_applicability == Some(Applicability::MachineApplicable))
+    }
+
     pub fn to_output(&self, format: OutputFormat) -> String {
         let span = self.span();
         let mut file = span.file_name.clone();
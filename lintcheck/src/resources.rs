@@ -0,0 +1,76 @@
+//! Measures the peak memory usage of the `cargo check` runs.
+//!
+//! The resource usage of a process returned by `wait4` includes the descendants it waited for, so
+//! the peak resident set size of `cargo` is the one of the largest `clippy-driver` it ran.
+
+#[cfg(not(unix))]
+use std::io;
+#[cfg(not(unix))]
+use std::process::{Command, Output};
+
+#[cfg(unix)]
+pub use wait4::output_with_max_rss;
+
+/// Runs `cmd` like [`Command::output`]. The peak memory usage can't be measured on this platform.
+#[cfg(not(unix))]
+pub fn output_with_max_rss(cmd: &mut Command) -> io::Result<(Output, Option<u64>)> {
+    Ok((cmd.output()?, None))
+}
+
+#[cfg(unix)]
+mod wait4 {
+    use std::io::{self, Read as _};
+    use std::os::unix::process::ExitStatusExt as _;
+    use std::process::{Command, ExitStatus, Output, Stdio};
+    use std::{mem, thread};
+
+    /// Runs `cmd` like [`Command::output`], also returning the peak resident set size of the
+    /// process and its descendants in kilobytes.
+    pub fn output_with_max_rss(cmd: &mut Command) -> io::Result<(Output, Option<u64>)> {
+        let mut child = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Both pipes are read at the same time so that the process doesn't block on a full one
+        let mut stderr_pipe = child.stderr.take().unwrap();
+        let stderr_thread = thread::spawn(move || {
+            let mut stderr = Vec::new();
+            stderr_pipe.read_to_end(&mut stderr).map(|_| stderr)
+        });
+        let mut stdout = Vec::new();
+        child.stdout.take().unwrap().read_to_end(&mut stdout)?;
+        let stderr = stderr_thread.join().unwrap()?;
+
+        let pid = libc::pid_t::try_from(child.id()).unwrap();
+        let mut status = 0;
+        // SAFETY: `rusage` only contains integers, for which zero is valid.
+        let mut rusage: libc::rusage = unsafe { mem::zeroed() };
+        loop {
+            // SAFETY: `status` and `rusage` are valid for writes, and `pid` is a child which hasn't
+            // been waited for.
+            if unsafe { libc::wait4(pid, &raw mut status, 0, &raw mut rusage) } >= 0 {
+                break;
+            }
+            let error = io::Error::last_os_error();
+            if error.kind() != io::ErrorKind::Interrupted {
+                return Err(error);
+            }
+        }
+
+        // Linux and the BSDs count in kilobytes, macOS in bytes
+        let max_rss = u64::try_from(rusage.ru_maxrss).unwrap_or_default();
+        let max_rss = if cfg!(target_os = "macos") {
+            max_rss / 1024
+        } else {
+            max_rss
+        };
+        let output = Output {
+            status: ExitStatus::from_raw(status),
+            stdout,
+            stderr,
+        };
+        Ok((output, Some(max_rss)))
+    }
+}
@@ -0,0 +1,408 @@
+//! The `sample` subcommand, which picks a reproducible random sample of crates from a local copy of
+//! the crates.io data, as the most downloaded crates are written by a few authors.
+//!
+//! The data is either the extracted database dump from <https://static.crates.io/db-dump.tar.gz>
+//! or a checkout of the index. The crates can only be sampled by category, edition, downloads or
+//! kind with the dump, the index only has their versions.
+
+use cargo_metadata::semver::Version;
+use clap::ValueEnum as _;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, BufRead as _, BufReader};
+use std::mem;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::config::Stratum;
+
+/// A crate which can be picked, with the latest version that isn't yanked or a prerelease.
+struct Candidate {
+    name: String,
+    version: Version,
+    /// The strata of the crate, several for the crates in several categories
+    strata: Vec<String>,
+}
+
+pub(crate) fn sample(
+    source: &Path,
+    output: &Path,
+    number: usize,
+    seed: u64,
+    by: Option<Stratum>,
+) -> Result<(), Box<dyn Error>> {
+    let mut candidates = if source.join("config.json").is_file() {
+        if by.is_some() {
+            return Err("the crates can only be sampled by stratum from a database dump, not the index".into());
+        }
+        read_index(source)?
+    } else if source.join("data").is_dir() {
+        read_dump(&source.join("data"), by)?
+    } else {
+        read_dump(source, by)?
+    };
+    // The files are read in an arbitrary order
+    candidates.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+    let mut crates = choose(&candidates, number, seed);
+    println!("Picked {} of {} crates", crates.len(), candidates.len());
+    crates.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+    let mut out = format!("# Sampled from {} with the seed {seed}", source.display());
+    if let Some(by) = by {
+        write!(out, " by {}", by.to_possible_value().unwrap().get_name()).unwrap();
+    }
+    out.push_str("\n[crates]\n");
+    for Candidate { name, version, .. } in crates {
+        writeln!(out, "{name} = {{ name = '{name}', version = '{version}' }}").unwrap();
+    }
+    fs::write(output, out)?;
+
+    Ok(())
+}
+
+/// Picks `number` crates, the same number in each stratum as long as they have enough crates.
+fn choose(candidates: &[Candidate], number: usize, seed: u64) -> Vec<&Candidate> {
+    let mut strata: BTreeMap<&str, Vec<&Candidate>> = BTreeMap::new();
+    for candidate in candidates {
+        if candidate.strata.is_empty() {
+            strata.entry("all").or_default().push(candidate);
+        }
+        for stratum in &candidate.strata {
+            strata.entry(stratum).or_default().push(candidate);
+        }
+    }
+
+    let mut rng = Rng(seed);
+    let mut strata: Vec<_> = strata
+        .into_values()
+        .map(|mut stratum| {
+            rng.shuffle(&mut stratum);
+            stratum.into_iter()
+        })
+        .collect();
+
+    let mut chosen = Vec::new();
+    let mut names = HashSet::new();
+    while chosen.len() < number && !strata.is_empty() {
+        strata.retain_mut(|stratum| {
+            if chosen.len() == number {
+                return true;
+            }
+            // A crate in several categories may already have been picked in another one
+            match stratum.find(|candidate| !names.contains(candidate.name.as_str())) {
+                Some(candidate) => {
+                    names.insert(candidate.name.as_str());
+                    chosen.push(candidate);
+                    true
+                },
+                None => false,
+            }
+        });
+    }
+    chosen
+}
+
+/// Parses `num` if it's the version of a release which isn't yanked.
+fn release(num: &str, yanked: bool) -> Option<Version> {
+    let version = Version::parse(num).ok()?;
+    (!yanked && version.pre.is_empty()).then_some(version)
+}
+
+/// Reads the crates of a checkout of the index, a file per crate with a JSON line per version.
+fn read_index(dir: &Path) -> Result<Vec<Candidate>, Box<dyn Error>> {
+    #[derive(Deserialize)]
+    struct Entry {
+        name: String,
+        vers: String,
+        yanked: bool,
+    }
+
+    let mut candidates = Vec::new();
+    for entry in WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.'))
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() || entry.file_name() == "config.json" {
+            continue;
+        }
+        let mut latest: Option<Candidate> = None;
+        for line in fs::read_to_string(entry.path())?.lines() {
+            let entry: Entry = serde_json::from_str(line)?;
+            if let Some(version) = release(&entry.vers, entry.yanked)
+                && latest.as_ref().is_none_or(|latest| version > latest.version)
+            {
+                latest = Some(Candidate {
+                    name: entry.name,
+                    version,
+                    strata: Vec::new(),
+                });
+            }
+        }
+        candidates.extend(latest);
+    }
+    Ok(candidates)
+}
+
+/// The latest version of a crate in the database dump.
+struct DumpVersion {
+    id: String,
+    version: Version,
+    /// The value of the column of the stratum, for the strata of the versions
+    stratum: String,
+}
+
+/// Reads the crates of the `data` directory of a database dump, along with their strata.
+fn read_dump(data: &Path, by: Option<Stratum>) -> Result<Vec<Candidate>, Box<dyn Error>> {
+    let mut names = HashMap::new();
+    let mut csv = Csv::open(data.join("crates.csv"))?;
+    let (id, name) = (csv.column("id")?, csv.column("name")?);
+    while csv.read_record()? {
+        names.insert(csv.take(id), csv.take(name));
+    }
+
+    let mut versions: HashMap<String, DumpVersion> = HashMap::new();
+    let mut csv = Csv::open(data.join("versions.csv"))?;
+    let (id, crate_id, num, yanked) = (
+        csv.column("id")?,
+        csv.column("crate_id")?,
+        csv.column("num")?,
+        csv.column("yanked")?,
+    );
+    let stratum = match by {
+        Some(Stratum::Edition) => Some(csv.column("edition")?),
+        Some(Stratum::Kind) => Some(csv.column("has_lib")?),
+        Some(Stratum::Category | Stratum::Downloads) | None => None,
+    };
+    while csv.read_record()? {
+        if let Some(version) = release(&csv.record[num], csv.record[yanked] == "t")
+            && versions
+                .get(&csv.record[crate_id])
+                .is_none_or(|latest| version > latest.version)
+        {
+            let latest = DumpVersion {
+                id: csv.take(id),
+                version,
+                stratum: stratum.map(|stratum| csv.take(stratum)).unwrap_or_default(),
+            };
+            versions.insert(csv.take(crate_id), latest);
+        }
+    }
+
+    let mut strata = read_strata(data, by, &names, &versions)?;
+    Ok(versions
+        .into_iter()
+        .filter_map(|(crate_id, version)| {
+            let crate_strata = strata.remove(&crate_id).unwrap_or_default();
+            Some(Candidate {
+                name: names.remove(&crate_id)?,
+                version: version.version,
+                strata: crate_strata,
+            })
+        })
+        .collect())
+}
+
+/// Reads the strata of the crates of the dump from the tables other than `versions`. Every crate of
+/// `versions` has at least one stratum if `by` is set.
+fn read_strata(
+    data: &Path,
+    by: Option<Stratum>,
+    names: &HashMap<String, String>,
+    versions: &HashMap<String, DumpVersion>,
+) -> Result<HashMap<String, Vec<String>>, Box<dyn Error>> {
+    let mut strata: HashMap<String, Vec<String>> = HashMap::new();
+    match by {
+        Some(Stratum::Category) => {
+            // Only the top level categories, `web-programming::http-client` is `web-programming`
+            let mut categories = HashMap::new();
+            let mut csv = Csv::open(data.join("categories.csv"))?;
+            let (id, slug) = (csv.column("id")?, csv.column("slug")?);
+            while csv.read_record()? {
+                let slug = csv.record[slug].split("::").next().unwrap_or_default().to_owned();
+                categories.insert(csv.take(id), slug);
+            }
+            let mut csv = Csv::open(data.join("crates_categories.csv"))?;
+            let (crate_id, category_id) = (csv.column("crate_id")?, csv.column("category_id")?);
+            while csv.read_record()? {
+                if let Some(category) = categories.get(&csv.record[category_id]) {
+                    let crate_categories = strata.entry(csv.take(crate_id)).or_default();
+                    if !crate_categories.contains(category) {
+                        crate_categories.push(category.clone());
+                    }
+                }
+            }
+            for crate_id in versions.keys() {
+                strata
+                    .entry(crate_id.clone())
+                    .or_insert_with(|| vec!["uncategorized".to_owned()]);
+            }
+        },
+        Some(Stratum::Edition) => {
+            for (crate_id, version) in versions {
+                let edition = if version.stratum.is_empty() {
+                    "2015"
+                } else {
+                    &version.stratum
+                };
+                strata.insert(crate_id.clone(), vec![edition.to_owned()]);
+            }
+        },
+        Some(Stratum::Downloads) => {
+            let mut csv = Csv::open(data.join("recent_crate_downloads.csv"))?;
+            let (crate_id, downloads) = (csv.column("crate_id")?, csv.column("downloads")?);
+            while csv.read_record()? {
+                let tier = downloads_tier(csv.record[downloads].parse()?);
+                strata.insert(csv.take(crate_id), vec![tier.to_owned()]);
+            }
+            for crate_id in versions.keys() {
+                strata
+                    .entry(crate_id.clone())
+                    .or_insert_with(|| vec![downloads_tier(0).to_owned()]);
+            }
+        },
+        Some(Stratum::Kind) => {
+            // The dump doesn't say which crates are procedural macros, they are recognized by their
+            // dependency on `proc-macro2`
+            let proc_macro2 = names.iter().find(|(_, name)| *name == "proc-macro2").map(|(id, _)| id);
+            let latest_ids: HashMap<&str, &str> = versions
+                .iter()
+                .map(|(crate_id, version)| (version.id.as_str(), crate_id.as_str()))
+                .collect();
+            let mut proc_macro_crates = HashSet::new();
+            let mut csv = Csv::open(data.join("dependencies.csv"))?;
+            let (version_id, crate_id, kind) =
+                (csv.column("version_id")?, csv.column("crate_id")?, csv.column("kind")?);
+            while csv.read_record()? {
+                // A normal dependency, not a build or dev one
+                if Some(&csv.record[crate_id]) == proc_macro2
+                    && csv.record[kind] == "0"
+                    && let Some(&dependent) = latest_ids.get(csv.record[version_id].as_str())
+                {
+                    proc_macro_crates.insert(dependent);
+                }
+            }
+            for (crate_id, version) in versions {
+                let kind = if proc_macro_crates.contains(crate_id.as_str()) {
+                    "proc-macro"
+                } else if version.stratum == "f" {
+                    "bin"
+                } else {
+                    "lib"
+                };
+                strata.insert(crate_id.clone(), vec![kind.to_owned()]);
+            }
+        },
+        None => {},
+    }
+    Ok(strata)
+}
+
+/// The tier of the downloads of the last 90 days.
+fn downloads_tier(downloads: u64) -> &'static str {
+    match downloads {
+        0..1_000 => "<1k",
+        1_000..10_000 => "1k-10k",
+        10_000..100_000 => "10k-100k",
+        100_000..1_000_000 => "100k-1M",
+        _ => ">1M",
+    }
+}
+
+/// A CSV file of the database dump, whose quoted fields may contain newlines, read record by
+/// record.
+struct Csv {
+    path: PathBuf,
+    reader: BufReader<File>,
+    header: Vec<String>,
+    record: Vec<String>,
+}
+
+impl Csv {
+    fn open(path: PathBuf) -> Result<Self, Box<dyn Error>> {
+        let file = File::open(&path).map_err(|e| format!("failed to open `{}`: {e}", path.display()))?;
+        let mut csv = Self {
+            path,
+            reader: BufReader::new(file),
+            header: Vec::new(),
+            record: Vec::new(),
+        };
+        csv.read_record()?;
+        csv.header = mem::take(&mut csv.record);
+        Ok(csv)
+    }
+
+    /// Returns the index of the column `name` in the records.
+    fn column(&self, name: &str) -> Result<usize, String> {
+        self.header
+            .iter()
+            .position(|column| column == name)
+            .ok_or_else(|| format!("`{}` has no `{name}` column", self.path.display()))
+    }
+
+    /// Takes the field at `column` out of the current record.
+    fn take(&mut self, column: usize) -> String {
+        mem::take(&mut self.record[column])
+    }
+
+    /// Reads the next record, returning `false` at the end of the file.
+    fn read_record(&mut self) -> io::Result<bool> {
+        self.record.clear();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 {
+                if self.record.is_empty() && field.is_empty() {
+                    return Ok(false);
+                }
+                break;
+            }
+            let mut chars = line.chars().peekable();
+            while let Some(c) = chars.next() {
+                match c {
+                    // A quote in a quoted field is doubled
+                    '"' if quoted && chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    },
+                    '"' => quoted = !quoted,
+                    ',' if !quoted => self.record.push(mem::take(&mut field)),
+                    '\r' | '\n' if !quoted => {},
+                    c => field.push(c),
+                }
+            }
+            if !quoted {
+                break;
+            }
+        }
+        self.record.push(field);
+        Ok(true)
+    }
+}
+
+/// The `SplitMix64` generator, so that the same seed gives the same sample on every platform.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Shuffles `items` with the Fisher-Yates shuffle.
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.next_u64() % (i as u64 + 1);
+            items.swap(i, usize::try_from(j).unwrap());
+        }
+    }
+}
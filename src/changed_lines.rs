@@ -0,0 +1,219 @@
+//! The lines changed since a Git revision or in a diff, for `cargo clippy --changed-since` and
+//! `--changed-lines`, which only report the lints pointing to them. They're passed to the driver,
+//! which drops the other lints.
+
+use clippy_config::clippy_args::{ChangedLines, FileChanges};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Where the changed lines come from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangeSource {
+    /// The changes of the working tree since a Git revision, set by `--changed-since`.
+    GitRevision(String),
+    /// A unified diff, set by `--changed-lines`.
+    DiffFile(PathBuf),
+}
+
+/// Reads the changed lines, the paths of the diffs being relative to the workspace `root`.
+pub fn load(source: &ChangeSource, root: &Path) -> Result<ChangedLines, String> {
+    let mut changed = ChangedLines::default();
+    match source {
+        ChangeSource::GitRevision(rev) => {
+            let diff = git(
+                root,
+                &[
+                    "diff",
+                    "--unified=0",
+                    "--no-color",
+                    "--no-ext-diff",
+                    "--relative",
+                    rev,
+                    "--",
+                ],
+            )?;
+            add_diff(&mut changed, root, &diff);
+            for path in git(root, &["ls-files", "--others", "--exclude-standard"])?.lines() {
+                changed.files.insert(root.join(path), FileChanges::All);
+            }
+        },
+        ChangeSource::DiffFile(path) => {
+            let diff =
+                fs::read_to_string(path).map_err(|e| format!("couldn't read the diff `{}`: {e}", path.display()))?;
+            add_diff(&mut changed, root, &diff);
+        },
+    }
+    Ok(changed)
+}
+
+/// Runs `git` in the workspace root and returns its output.
+fn git(root: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .current_dir(root)
+        .args(args)
+        .output()
+        .map_err(|e| format!("couldn't run `git`: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    String::from_utf8(output.stdout).map_err(|e| format!("`git {}` printed invalid UTF-8: {e}", args.join(" ")))
+}
+
+/// Adds the lines added or modified by a unified diff, with or without context lines.
+fn add_diff(changed: &mut ChangedLines, root: &Path, diff: &str) {
+    let mut lines: Option<&mut Vec<(usize, usize)>> = None;
+    let mut next_line = 0;
+    // The lines of the current hunk left to read in the old and the new file. The lines of a
+    // hunk are only read as such, as an added `++ x` or a removed `-- x` looks like a header.
+    let (mut old_left, mut new_left) = (0usize, 0usize);
+    for diff_line in diff.lines() {
+        if old_left > 0 || new_left > 0 {
+            match diff_line.as_bytes().first() {
+                Some(b'+') => {
+                    if let Some(lines) = &mut lines {
+                        add_line(lines, next_line);
+                    }
+                    next_line += 1;
+                    new_left = new_left.saturating_sub(1);
+                },
+                Some(b'-') => old_left = old_left.saturating_sub(1),
+                // `\ No newline at end of file`
+                Some(b'\\') => {},
+                // A context line, which may have lost its space if it's empty
+                _ => {
+                    next_line += 1;
+                    old_left = old_left.saturating_sub(1);
+                    new_left = new_left.saturating_sub(1);
+                },
+            }
+        } else if let Some(path) = diff_line.strip_prefix("+++ ") {
+            // Some tools add the modification time after a tab.
+            let path = path.split('\t').next().unwrap_or(path);
+            lines = if path == "/dev/null" {
+                None
+            } else {
+                let path = root.join(path.strip_prefix("b/").unwrap_or(path));
+                match changed
+                    .files
+                    .entry(path)
+                    .or_insert_with(|| FileChanges::Lines(Vec::new()))
+                {
+                    FileChanges::Lines(lines) => Some(lines),
+                    FileChanges::All => None,
+                }
+            };
+        } else if let Some(hunk) = diff_line.strip_prefix("@@ ")
+            && let Some((new_start, old_count, new_count)) = parse_hunk_header(hunk)
+        {
+            next_line = new_start;
+            old_left = old_count;
+            new_left = new_count;
+        }
+    }
+}
+
+/// Adds `line` to the ranges of changed lines, extending the last one if it's the next line.
+fn add_line(lines: &mut Vec<(usize, usize)>, line: usize) {
+    match lines.last_mut() {
+        Some((_, end)) if *end + 1 == line => *end = line,
+        _ => lines.push((line, line)),
+    }
+}
+
+/// Parses the ranges of a hunk header, `-old_start,old_count +new_start,new_count @@`, into the
+/// first line of the new file and the line counts, which are 1 when they're omitted.
+fn parse_hunk_header(hunk: &str) -> Option<(usize, usize, usize)> {
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let mut ranges = hunk.split_whitespace();
+    let (_, old_count) = parse_range(ranges.next()?.strip_prefix('-')?)?;
+    let (new_start, new_count) = parse_range(ranges.next()?.strip_prefix('+')?)?;
+    Some((new_start, old_count, new_count))
+}
+
+/// Returns the root of the workspace Cargo runs in, which rustc gives the paths of the workspace
+/// files relative to.
+pub fn workspace_root(cargo_args: &[String]) -> Result<PathBuf, String> {
+    let mut cmd = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+    cmd.args(["locate-project", "--workspace", "--message-format", "plain"]);
+    if let Some(path) = manifest_path(cargo_args) {
+        cmd.arg("--manifest-path").arg(path);
+    }
+    let output = cmd
+        .output()
+        .map_err(|e| format!("couldn't run `cargo locate-project`: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "couldn't find the workspace root: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let manifest = String::from_utf8_lossy(&output.stdout);
+    Ok(Path::new(manifest.trim())
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default())
+}
+
+/// Returns the `--manifest-path` given to Cargo, if any.
+pub fn manifest_path(cargo_args: &[String]) -> Option<&str> {
+    let mut args = cargo_args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--manifest-path" {
+            return args.next().map(String::as_str);
+        } else if let Some(path) = arg.strip_prefix("--manifest-path=") {
+            return Some(path);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::add_diff;
+    use clippy_config::clippy_args::{ChangedLines, FileChanges};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn add_diff_lines() {
+        let diff = "\
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,4 @@
+ fn f() {}
+-fn g() {}
++fn h() {}
+++++ x
+ fn i() {}
+@@ -10 +11 @@
+--- a/x
++++ b/x
+\\ No newline at end of file
+--- /dev/null
++++ b/src/new.rs
+@@ -0,0 +1,2 @@
++// new
++
+";
+        let mut changed = ChangedLines::default();
+        add_diff(&mut changed, Path::new("/root"), diff);
+        assert_eq!(changed.files.len(), 2, "{changed:?}");
+        assert_eq!(
+            changed.files[&PathBuf::from("/root/src/lib.rs")],
+            FileChanges::Lines(vec![(2, 3), (11, 11)])
+        );
+        assert_eq!(
+            changed.files[&PathBuf::from("/root/src/new.rs")],
+            FileChanges::Lines(vec![(1, 2)])
+        );
+    }
+}
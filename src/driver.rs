@@ -7,6 +7,7 @@
 // FIXME: switch to something more ergonomic here, once available.
 // (Currently there is no way to opt into sysroot crates without `extern crate`.)
 extern crate rustc_driver;
+extern crate rustc_errors;
 extern crate rustc_interface;
 extern crate rustc_session;
 extern crate rustc_span;
@@ -20,18 +21,28 @@ extern crate rustc_span;
 #[cfg(feature = "jemalloc")]
 extern crate tikv_jemalloc_sys as _;
 
+use clippy_config::baseline::{self, Baseline, Key, Record};
+use clippy_config::clippy_args::{ChangedLines, ClippyArgs, Level};
+use clippy_config::{Conf, ConfLocation};
+use clippy_lints::pass_profile::PassProfile;
+use clippy_utils::diagnostics::{FixFilter, clippy_lint};
 use clippy_utils::sym;
 use declare_clippy_lint::LintListBuilder;
+use rustc_errors::DiagInner;
 use rustc_interface::interface;
 use rustc_session::config::ErrorOutputType;
 use rustc_session::{EarlyDiagCtxt, Session};
+use rustc_span::source_map::get_source_map;
 use rustc_span::symbol::Symbol;
+use rustc_span::{FileName, Span};
 
 use std::env;
-use std::fs::read_to_string;
-use std::io::Write as _;
-use std::path::Path;
-use std::process::ExitCode;
+use std::fs::{OpenOptions, read_to_string};
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitCode};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// If a command-line option matches `find_arg`, then apply the predicate `pred` on its value. If
 /// true, then return it. The parameter is assumed to be either `--arg=value` or `--arg value`.
@@ -88,6 +99,24 @@ fn test_has_arg() {
     );
 }
 
+#[test]
+fn test_profile_output() {
+    assert_eq!(ProfileOutput::from_env_value(""), Ok(None));
+    assert_eq!(ProfileOutput::from_env_value("0"), Ok(None));
+    assert_eq!(ProfileOutput::from_env_value("1"), Ok(Some(ProfileOutput::Table)));
+    assert_eq!(
+        ProfileOutput::from_env_value("json"),
+        Ok(Some(ProfileOutput::Json(None)))
+    );
+    assert_eq!(
+        ProfileOutput::from_env_value("json:/tmp/profile.jsonl"),
+        Ok(Some(ProfileOutput::Json(Some("/tmp/profile.jsonl".into()))))
+    );
+    for value in ["true", "false", "no", "json:", "jsn:/tmp/profile.jsonl"] {
+        assert!(ProfileOutput::from_env_value(value).is_err(), "{value}");
+    }
+}
+
 fn track_clippy_args(sess: &Session, args_env_var: Option<&str>) {
     sess.env_depinfo
         .borrow_mut()
@@ -138,17 +167,88 @@ impl rustc_driver::Callbacks for RustcCallbacks {
 
 struct ClippyCallbacks {
     clippy_args_var: Option<String>,
+    /// The configuration file, looked up in the directory set by `cargo clippy --conf-dir` or
+    /// `CLIPPY_CONF_DIR`
+    conf_path: Option<io::Result<(Option<ConfLocation>, Vec<String>)>>,
+    /// Set by `cargo clippy --print-config`
+    print_config: bool,
+    /// Set by `cargo clippy` or `CLIPPY_PROFILE`
+    profile: Option<PassProfile>,
+    /// The baseline of `cargo clippy --baseline`, whose changes make Cargo lint the crates again
+    baseline: Option<PathBuf>,
+}
+
+/// Where to write the lint pass times, as set by `CLIPPY_PROFILE`.
+#[derive(Debug, PartialEq, Eq)]
+enum ProfileOutput {
+    /// A table on stderr, for `1`.
+    Table,
+    /// A JSON object per crate on stderr for `json`, or appended to a file for `json:<FILE>`.
+    Json(Option<PathBuf>),
+}
+
+impl ProfileOutput {
+    /// Parses the value of `CLIPPY_PROFILE`, an empty value or `0` disables the profiling.
+    fn from_env_value(value: &str) -> Result<Option<Self>, String> {
+        match value {
+            "" | "0" => Ok(None),
+            "1" => Ok(Some(Self::Table)),
+            "json" => Ok(Some(Self::Json(None))),
+            _ if let Some(path) = value.strip_prefix("json:")
+                && !path.is_empty() =>
+            {
+                Ok(Some(Self::Json(Some(path.into()))))
+            },
+            _ => Err(format!(
+                "invalid value `{value}` for `CLIPPY_PROFILE`, expected `1`, `json` or `json:<FILE>`"
+            )),
+        }
+    }
+
+    /// Writes the times recorded in `profile` for the crate compiled with `args`.
+    fn write(&self, profile: &PassProfile, args: &[String]) -> io::Result<()> {
+        let crate_name = args
+            .iter()
+            .position(|arg| arg == "--crate-name")
+            .and_then(|pos| args.get(pos + 1))
+            .cloned()
+            .or_else(|| env::var("CARGO_CRATE_NAME").ok())
+            .unwrap_or_else(|| "<unknown>".to_string());
+        let test = args.iter().any(|arg| arg == "--test");
+        match self {
+            // A single write keeps the table together when Cargo runs several crates in parallel
+            Self::Table => io::stderr().write_all(profile.take_table(&crate_name, test).as_bytes()),
+            Self::Json(None) => {
+                io::stderr().write_all(format!("{}\n", profile.take_json(&crate_name, test)).as_bytes())
+            },
+            Self::Json(Some(path)) => OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?
+                .write_all(format!("{}\n", profile.take_json(&crate_name, test)).as_bytes()),
+        }
+    }
 }
 
 impl rustc_driver::Callbacks for ClippyCallbacks {
     #[expect(rustc::bad_opt_access, reason = "necessary in clippy driver to set `mir_opt_level`")]
     fn config(&mut self, config: &mut interface::Config) {
-        let conf_path = clippy_config::lookup_conf_file();
+        let conf_path = self.conf_path.take().unwrap_or_else(clippy_config::lookup_conf_file);
         let previous = config.register_lints.take();
         let clippy_args_var = self.clippy_args_var.take();
+        let print_config = self.print_config;
+        let profile = self.profile.clone();
+        let baseline = self.baseline.take();
+        // Called once rustc set up the tracking of the diagnostics, which Clippy's wraps
+        config.psess_created = Some(Box::new(|_| clippy_utils::diagnostics::track_diagnostics()));
         config.track_state = Some(Box::new(move |sess| {
             track_clippy_args(sess, clippy_args_var.as_deref());
             track_files(sess);
+            if let Some(baseline) = &baseline {
+                sess.file_depinfo
+                    .borrow_mut()
+                    .insert(Symbol::intern(&baseline.to_string_lossy()));
+            }
 
             // Trigger a rebuild if CLIPPY_CONF_DIR changes. The value must be a valid string so
             // changes between dirs that are invalid UTF-8 will not trigger rebuilds
@@ -156,6 +256,11 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
                 sym::CLIPPY_CONF_DIR,
                 env::var("CLIPPY_CONF_DIR").ok().map(|dir| Symbol::intern(&dir)),
             ));
+            // Lint the crates again when `CLIPPY_PROFILE` is set or changed
+            sess.env_depinfo.borrow_mut().insert((
+                sym::CLIPPY_PROFILE,
+                env::var("CLIPPY_PROFILE").ok().map(|value| Symbol::intern(&value)),
+            ));
         }));
         config.register_lints = Some(Box::new(move |sess, lint_store| {
             // technically we're ~guaranteed that this is none but might as well call anything that
@@ -169,7 +274,18 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
             list_builder.register(lint_store);
 
             let conf = clippy_config::Conf::read(sess, &conf_path);
-            clippy_lints::register_lint_passes(lint_store, conf);
+            let lints: Vec<String> = clippy_lints::declared_lints::LINTS
+                .iter()
+                .map(|info| info.lint.name_lower())
+                .collect();
+            conf.check_override_lints(sess, &lints);
+            clippy_utils::diagnostics::set_allowed_by_conf(|sess, span, lint| {
+                conf.allows_lint(sess, span, &lint.name_lower())
+            });
+            if print_config {
+                print_conf(sess, &conf_path, conf);
+            }
+            clippy_lints::register_lint_passes(lint_store, conf, profile.clone());
 
             #[cfg(feature = "internal")]
             clippy_lints_internal::register_lints(lint_store);
@@ -189,6 +305,22 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
     }
 }
 
+/// Prints the configuration of the crate being compiled, for `cargo clippy --print-config`
+fn print_conf(sess: &Session, conf_path: &io::Result<(Option<ConfLocation>, Vec<String>)>, conf: &Conf) {
+    let crate_name = env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "<unknown>".to_string());
+    let source = match conf_path {
+        Ok((Some(location), _)) => format!("read from {location}"),
+        Ok((None, _)) => "no configuration file found".to_string(),
+        Err(_) => "the configuration file couldn't be found".to_string(),
+    };
+    // Errors are ignored as for `--help`, there's nothing better to do
+    let _ = writeln!(
+        &mut anstream::stdout().lock(),
+        "# Clippy configuration of `{crate_name}`, {source}\n\n{}",
+        conf.to_annotated_toml(sess)
+    );
+}
+
 fn display_help() -> ExitCode {
     if writeln!(&mut anstream::stdout().lock(), "{}", help_message()).is_err() {
         ExitCode::FAILURE
@@ -278,39 +410,34 @@ fn main() -> ExitCode {
         let mut args: Vec<String> = orig_args.clone();
         pass_sysroot_env_if_given(&mut args, sys_root_env);
 
-        let mut no_deps = false;
+        // Do not run Clippy for Cargo's info queries so that invalid CLIPPY_ARGS are not cached
+        // https://github.com/rust-lang/cargo/issues/14385
+        let info_query = args.iter().any(|arg| arg == "-vV")
+            || arg_value(&orig_args, "--print", |val| val != "crate-root-lint-levels");
+
         let clippy_args_var = env::var("CLIPPY_ARGS").ok();
-        let clippy_args = clippy_args_var
-            .as_deref()
-            .unwrap_or_default()
-            .split("__CLIPPY_HACKERY__")
-            .filter_map(|s| match s {
-                "" => None,
-                "--no-deps" => {
-                    no_deps = true;
-                    None
-                },
-                _ => Some(s.to_string()),
-            })
-            .chain(vec!["--cfg".into(), "clippy".into()])
-            .collect::<Vec<String>>();
+        let clippy_args = match clippy_args_var.as_deref() {
+            Some(value) if !value.is_empty() && !info_query => {
+                let version = rustc_tools_util::get_version_info!().to_string();
+                ClippyArgs::from_env_value(value, &version).unwrap_or_else(|e| early_dcx.early_fatal(e))
+            },
+            _ => ClippyArgs::default(),
+        };
 
         // If no Clippy lints will be run we do not need to run Clippy
         let cap_lints_allow = arg_value(&orig_args, "--cap-lints", |val| val == "allow")
-            && !arg_value(&orig_args, "--force-warn", |val| val.contains("clippy::"));
+            && !arg_value(&orig_args, "--force-warn", |val| val.contains("clippy::"))
+            && !clippy_args
+                .lint_levels
+                .iter()
+                .any(|lint_level| lint_level.level == Level::ForceWarn && lint_level.lint.contains("clippy::"));
 
         // If `--no-deps` is enabled only lint the primary package
-        let relevant_package = !no_deps || env::var("CARGO_PRIMARY_PACKAGE").is_ok();
-
-        // Do not run Clippy for Cargo's info queries so that invalid CLIPPY_ARGS are not cached
-        // https://github.com/rust-lang/cargo/issues/14385
-        let info_query = args.iter().any(|arg| arg == "-vV")
-            || arg_value(&orig_args, "--print", |val| val != "crate-root-lint-levels");
+        let relevant_package = !clippy_args.no_deps || env::var("CARGO_PRIMARY_PACKAGE").is_ok();
 
         let clippy_enabled = !cap_lints_allow && relevant_package && !info_query;
         if clippy_enabled {
-            args.extend(clippy_args);
-            rustc_driver::run_compiler(&args, &mut ClippyCallbacks { clippy_args_var });
+            run_clippy(&early_dcx, args, &clippy_args, clippy_args_var);
         } else {
             rustc_driver::run_compiler(&args, &mut RustcCallbacks { clippy_args_var });
         }
@@ -318,6 +445,171 @@ fn main() -> ExitCode {
     })
 }
 
+/// Compiles the crate with `args` and Clippy's lints, set up with the arguments of `cargo clippy`.
+fn run_clippy(
+    early_dcx: &EarlyDiagCtxt,
+    mut args: Vec<String>,
+    clippy_args: &ClippyArgs,
+    clippy_args_var: Option<String>,
+) {
+    let conf_dir = clippy_args
+        .conf_dir
+        .clone()
+        .or_else(|| env::var_os("CLIPPY_CONF_DIR").map(PathBuf::from));
+    let conf_path = clippy_config::lookup_conf_file_in(conf_dir);
+    // The profile comes after the `[lints]` of `Cargo.toml` and before the arguments given
+    // after `--`, which take precedence over it.
+    if let Some(name) = &clippy_args.lint_profile {
+        let profile = match &conf_path {
+            Ok((Some(location), _)) => clippy_config::read_lint_profile(name, location),
+            _ => None,
+        };
+        // A workspace may select a profile that some of its crates don't have, their
+        // configuration errors are reported by the session.
+        match profile {
+            Some(profile) => args.extend(profile.lint_args()),
+            None => early_dcx.early_warn(format!(
+                "no lint profile named `{name}` in Clippy's configuration, using the default lint levels"
+            )),
+        }
+    }
+    args.extend(clippy_args.compiler_args());
+    args.extend(["--cfg".into(), "clippy".into()]);
+    let baseline = set_diagnostic_filters(early_dcx, clippy_args);
+    let profile_output = clippy_args
+        .profile
+        .clone()
+        .or_else(|| env::var("CLIPPY_PROFILE").ok())
+        .and_then(|value| {
+            ProfileOutput::from_env_value(&value).unwrap_or_else(|e| {
+                early_dcx.early_warn(e);
+                None
+            })
+        });
+    let profile = profile_output.as_ref().map(|_| PassProfile::default());
+    rustc_driver::run_compiler(
+        &args,
+        &mut ClippyCallbacks {
+            clippy_args_var,
+            conf_path: Some(conf_path),
+            print_config: clippy_args.print_config.is_some(),
+            profile: profile.clone(),
+            baseline: clippy_args.baseline.clone(),
+        },
+    );
+    if let Some(baseline) = baseline
+        && let Some(path) = &clippy_args.baseline
+        && baseline.suppressed() > 0
+    {
+        early_dcx.early_note(format!(
+            "suppressed {} recorded in the baseline `{}`",
+            baseline::plural_diagnostics(baseline.suppressed()),
+            path.display()
+        ));
+    }
+    if let Some(output) = profile_output
+        && let Some(profile) = profile
+        && let Err(e) = output.write(&profile, &args)
+    {
+        early_dcx.early_warn(format!("couldn't write the Clippy lint pass times: {e}"));
+    }
+}
+
+/// Sets up the processing of the diagnostics asked for by `cargo clippy`: the suggestions that
+/// `--fix-only` applies, and the lints that `--baseline` and `--changed-since` drop. Returns the
+/// baseline, which tells how many diagnostics it dropped.
+fn set_diagnostic_filters(early_dcx: &EarlyDiagCtxt, clippy_args: &ClippyArgs) -> Option<Arc<Baseline>> {
+    clippy_utils::diagnostics::set_fix_filter(FixFilter {
+        lints: clippy_args.fix_only.clone(),
+        maybe_incorrect: clippy_args.fix_maybe_incorrect,
+    });
+    let baseline = clippy_args
+        .baseline
+        .as_deref()
+        .map(|path| Arc::new(Baseline::load(path).unwrap_or_else(|e| early_dcx.early_fatal(e))));
+    let records = clippy_args.baseline_records.clone();
+    let changed_lines = clippy_args.changed_lines.clone();
+    if baseline.is_none() && records.is_none() && changed_lines.is_none() {
+        return None;
+    }
+
+    let unit = format!(
+        "{}-{}",
+        process::id(),
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+    );
+    let filter_baseline = baseline.clone();
+    clippy_utils::diagnostics::set_lint_filter(move |diag| {
+        if let Some((key, message)) = baseline_key(diag) {
+            if let Some(records) = &records {
+                // The diagnostic is still reported if it can't be recorded
+                return Record {
+                    unit: unit.clone(),
+                    key,
+                    message,
+                }
+                .append(records)
+                .is_ok();
+            }
+            if filter_baseline
+                .as_ref()
+                .is_some_and(|baseline| baseline.suppresses(&key))
+            {
+                return true;
+            }
+        }
+        changed_lines
+            .as_ref()
+            .is_some_and(|changed_lines| !in_changed_lines(changed_lines, diag))
+    });
+    baseline
+}
+
+/// Returns the key of a Clippy diagnostic in the baseline, along with its message. The other
+/// diagnostics are never part of the baseline.
+fn baseline_key(diag: &DiagInner) -> Option<(Key, String)> {
+    let lint = clippy_lint(diag)?;
+    let span = diag.span.primary_span().filter(|span| !span.is_dummy())?;
+    let source_map = get_source_map()?;
+    let file = source_map.lookup_char_pos(span.lo()).file;
+    let file = source_map.filename_for_diagnostics(&file.name).to_string();
+    // The whole lines when the span is empty
+    let snippet = source_map
+        .span_to_snippet(span)
+        .ok()
+        .filter(|snippet| !snippet.trim().is_empty())
+        .or_else(|| source_map.span_to_snippet(source_map.span_extend_to_line(span)).ok())?;
+    let message = diag.messages.first().and_then(|(message, _)| message.as_str());
+    Some((Key::new(lint, &file, &snippet), message.unwrap_or_default().to_owned()))
+}
+
+/// Whether the primary span of the diagnostic overlaps the changed lines. The diagnostics without
+/// a location are always kept.
+fn in_changed_lines(changed_lines: &ChangedLines, diag: &DiagInner) -> bool {
+    let Some(span) = diag.span.primary_span().filter(|span| !span.is_dummy()) else {
+        return true;
+    };
+    span_path(span).is_some_and(|(path, start, end)| changed_lines.contains(&path, start, end))
+}
+
+/// Returns the absolute path of the file containing `span`, along with its first and last lines.
+fn span_path(span: Span) -> Option<(PathBuf, usize, usize)> {
+    let source_map = get_source_map()?;
+    let start = source_map.lookup_char_pos(span.lo());
+    let end = source_map.lookup_char_pos(span.hi());
+    if let FileName::Real(name) = &start.file.name
+        && let Some(path) = name.local_path()
+        && let Some(working_dir) = source_map.working_dir().local_path()
+    {
+        Some((working_dir.join(path), start.line, end.line))
+    } else {
+        None
+    }
+}
+
 #[must_use]
 fn help_message() -> &'static str {
     color_print::cstr!(
@@ -4,12 +4,37 @@
 // warn on lints, that are included in `rust-lang/rust`s bootstrap
 #![warn(rust_2018_idioms, unused_lifetimes)]
 
+extern crate rustc_ast;
 extern crate rustc_driver;
-
-use std::env;
-use std::io::Write as _;
+extern crate rustc_interface;
+extern crate rustc_session;
+extern crate rustc_span;
+
+mod changed_lines;
+mod sarif;
+mod summary;
+mod watch;
+
+use cargo_metadata::Message;
+use cargo_metadata::diagnostic::Diagnostic;
+use changed_lines::ChangeSource;
+use clippy_config::clippy_args::ClippyArgs;
+use clippy_config::{Conf, baseline};
+use clippy_utils::sym;
+use rustc_ast::ast;
+use rustc_driver::Compilation;
+use rustc_interface::interface;
+use rustc_session::config::Input;
+use rustc_span::FileName;
+use sarif::SarifLog;
+use std::collections::HashSet;
+use std::io::{self, BufReader, IsTerminal as _, Write as _};
 use std::path::PathBuf;
-use std::process::{self, Command, exit};
+use std::process::{self, Command, Stdio, exit};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+use summary::Summary;
+use watch::{WatchedFiles, Watcher};
 
 fn show_help() {
     if writeln!(&mut anstream::stdout().lock(), "{}", help_message()).is_err() {
@@ -41,6 +66,7 @@ pub fn main() {
             lint.make_ascii_lowercase();
             process::exit(clippy_lints::explain(
                 &lint.strip_prefix("clippy::").unwrap_or(&lint).replace('-', "_"),
+                read_conf(),
             ));
         } else {
             show_help();
@@ -48,34 +74,202 @@ pub fn main() {
         return;
     }
 
+    if env::args().any(|a| a == "--list-lints") {
+        let json = match env::args().skip_while(|a| a != "--format").nth(1).as_deref() {
+            None | Some("text") => false,
+            Some("json") => true,
+            Some(format) => {
+                eprintln!("error: unknown format `{format}`, expected `text` or `json`");
+                exit(rustc_driver::EXIT_FAILURE);
+            },
+        };
+        process::exit(clippy_lints::list_lints(json));
+    }
+
+    if env::args().any(|a| a == "--config-schema") {
+        let schema = clippy_config::configuration_json_schema();
+        if writeln!(&mut anstream::stdout().lock(), "{schema:#}").is_err() {
+            exit(rustc_driver::EXIT_FAILURE);
+        }
+        return;
+    }
+
     if let Err(code) = process(env::args().skip(2)) {
         process::exit(code);
     }
 }
 
+/// Reads the configuration of the current directory for `--explain`, or returns `None` if it
+/// couldn't be read. The errors of the configuration file are reported by a compiler session,
+/// which stops before compiling anything.
+fn read_conf() -> Option<&'static Conf> {
+    #[derive(Default)]
+    struct ReadConf(Option<&'static Conf>);
+
+    impl rustc_driver::Callbacks for ReadConf {
+        fn config(&mut self, config: &mut interface::Config) {
+            config.input = Input::Str {
+                name: FileName::Custom("explain".into()),
+                input: String::new(),
+            };
+            config.extra_symbols = sym::EXTRA_SYMBOLS.into();
+        }
+
+        fn after_crate_root_parsing(&mut self, compiler: &interface::Compiler, _: &mut ast::Crate) -> Compilation {
+            let conf = Conf::read(&compiler.sess, &clippy_config::lookup_conf_file());
+            if compiler.sess.dcx().has_errors().is_none() {
+                self.0 = Some(conf);
+            }
+            Compilation::Stop
+        }
+    }
+
+    let mut callbacks = ReadConf::default();
+    // The input is replaced by an empty string, the file name is only needed to parse the arguments
+    let args = ["clippy-driver", "explain.rs", "--crate-type=lib"].map(String::from);
+    let _ = rustc_driver::catch_with_exit_code(|| rustc_driver::run_compiler(&args, &mut callbacks));
+    callbacks.0
+}
+
+#[derive(Clone)]
+#[expect(clippy::struct_excessive_bools)]
 struct ClippyCmd {
     cargo_subcommand: &'static str,
     args: Vec<String>,
-    clippy_args: Vec<String>,
+    /// The arguments of `clippy-driver`, passed in `CLIPPY_ARGS`.
+    clippy_args: ClippyArgs,
+    print_config: bool,
+    /// The file to write the SARIF log to, set by `--sarif`.
+    sarif: Option<PathBuf>,
+    /// The baseline of diagnostics not to report, set by `--baseline`.
+    baseline: Option<PathBuf>,
+    /// Whether to record the diagnostics in the baseline instead, set by `--write-baseline`.
+    write_baseline: bool,
+    /// The changes to report the diagnostics of, set by `--changed-since` or `--changed-lines`.
+    changes: Option<ChangeSource>,
+    /// Whether to print the lint counts at the end, set by `--summary`.
+    summary: bool,
+    /// Whether to run again after every change of the files, set by `--watch`.
+    watch: bool,
+    /// The lints to apply the suggestions of, set by `--fix-only`. The names are normalized,
+    /// without the `clippy::` prefix.
+    fix_only: Option<Vec<String>>,
+    /// The least certain suggestions to apply, set by `--fix-applicability`.
+    fix_applicability: Option<String>,
 }
 
 impl ClippyCmd {
+    #[expect(clippy::too_many_lines)]
     fn new<I>(mut old_args: I) -> Self
     where
         I: Iterator<Item = String>,
     {
         let mut cargo_subcommand = "check";
         let mut args = vec![];
-        let mut clippy_args: Vec<String> = vec![];
-
-        for arg in old_args.by_ref() {
+        let mut clippy_args = ClippyArgs::new(rustc_tools_util::get_version_info!().to_string());
+        let mut print_config = false;
+        let mut sarif = None;
+        let mut baseline = None;
+        let mut write_baseline = false;
+        let mut changes = None;
+        let mut summary = false;
+        let mut watch = false;
+        let mut fix_only = None;
+        let mut fix_applicability = None;
+
+        while let Some(arg) = old_args.next() {
             match arg.as_str() {
                 "--fix" => {
                     cargo_subcommand = "fix";
                     continue;
                 },
+                "--fix-only" => {
+                    cargo_subcommand = "fix";
+                    // A missing list is reported like an empty one
+                    fix_only = Some(old_args.next().map(|lints| parse_lint_list(&lints)).unwrap_or_default());
+                    continue;
+                },
+                _ if let Some(lints) = arg.strip_prefix("--fix-only=") => {
+                    cargo_subcommand = "fix";
+                    fix_only = Some(parse_lint_list(lints));
+                    continue;
+                },
+                "--fix-applicability" => {
+                    fix_applicability = old_args.next();
+                    continue;
+                },
+                _ if let Some(applicability) = arg.strip_prefix("--fix-applicability=") => {
+                    fix_applicability = Some(applicability.into());
+                    continue;
+                },
+                "--print-config" => {
+                    print_config = true;
+                    continue;
+                },
+                "--sarif" => {
+                    sarif = old_args.next().map(PathBuf::from);
+                    continue;
+                },
+                _ if let Some(path) = arg.strip_prefix("--sarif=") => {
+                    sarif = Some(PathBuf::from(path));
+                    continue;
+                },
+                "--baseline" => {
+                    baseline = old_args.next().map(PathBuf::from);
+                    continue;
+                },
+                _ if let Some(path) = arg.strip_prefix("--baseline=") => {
+                    baseline = Some(PathBuf::from(path));
+                    continue;
+                },
+                "--write-baseline" => {
+                    write_baseline = true;
+                    continue;
+                },
+                "--changed-since" => {
+                    changes = old_args.next().map(ChangeSource::GitRevision);
+                    continue;
+                },
+                _ if let Some(rev) = arg.strip_prefix("--changed-since=") => {
+                    changes = Some(ChangeSource::GitRevision(rev.into()));
+                    continue;
+                },
+                "--changed-lines" => {
+                    changes = old_args.next().map(|path| ChangeSource::DiffFile(path.into()));
+                    continue;
+                },
+                _ if let Some(path) = arg.strip_prefix("--changed-lines=") => {
+                    changes = Some(ChangeSource::DiffFile(path.into()));
+                    continue;
+                },
+                "--summary" => {
+                    summary = true;
+                    continue;
+                },
+                "--watch" => {
+                    watch = true;
+                    continue;
+                },
                 "--no-deps" => {
-                    clippy_args.push("--no-deps".into());
+                    clippy_args.no_deps = true;
+                    continue;
+                },
+                // Read by `clippy-driver`, which looks the profile up in the configuration of each crate
+                "--lint-profile" => {
+                    clippy_args.lint_profile = old_args.next();
+                    continue;
+                },
+                _ if let Some(profile) = arg.strip_prefix("--lint-profile=") => {
+                    clippy_args.lint_profile = Some(profile.into());
+                    continue;
+                },
+                // Made absolute as `clippy-driver` runs in the directory of each package
+                "--conf-dir" => {
+                    clippy_args.conf_dir = old_args.next().map(absolute_path);
+                    continue;
+                },
+                _ if let Some(dir) = arg.strip_prefix("--conf-dir=") => {
+                    clippy_args.conf_dir = Some(absolute_path(dir));
                     continue;
                 },
                 "--" => break,
@@ -85,18 +279,64 @@ impl ClippyCmd {
             args.push(arg);
         }
 
-        clippy_args.append(&mut (old_args.collect()));
-        if cargo_subcommand == "fix" && !clippy_args.iter().any(|arg| arg == "--no-deps") {
-            clippy_args.push("--no-deps".into());
-        }
+        // `--no-deps` can also be given after `--`, where it's documented
+        let (no_deps, rustc_args): (Vec<String>, Vec<String>) = old_args.partition(|arg| arg == "--no-deps");
+        clippy_args.no_deps |= !no_deps.is_empty() || cargo_subcommand == "fix";
+        clippy_args.add_rustc_args(rustc_args);
+        // Read by `clippy-driver`, which changes the applicability of the suggestions
+        clippy_args.fix_only.clone_from(&fix_only);
+        clippy_args.fix_maybe_incorrect = fix_applicability.as_deref() == Some("maybe-incorrect");
 
         Self {
             cargo_subcommand,
             args,
             clippy_args,
+            print_config,
+            sarif,
+            baseline,
+            write_baseline,
+            changes,
+            summary,
+            watch,
+            fix_only,
+            fix_applicability,
         }
     }
 
+    /// Whether Cargo's JSON messages are needed to process the diagnostics before printing them.
+    fn json_diagnostics(&self) -> bool {
+        self.sarif.is_some() || self.summary || self.watch
+    }
+
+    /// Reads the lines changed since the revision of `--changed-since` or by the diff of
+    /// `--changed-lines`, which the driver reports the lints of. It's done again on every run of
+    /// `--watch`.
+    fn load_changes(&mut self) -> Result<(), String> {
+        if let Some(changes) = &self.changes {
+            let root = changed_lines::workspace_root(&self.args)?;
+            self.clippy_args.changed_lines = Some(changed_lines::load(changes, &root)?);
+        }
+        Ok(())
+    }
+
+    /// Passes the baseline of `--baseline` to the driver, which drops the diagnostics it contains,
+    /// or with `--write-baseline` the file to record the diagnostics in.
+    fn load_baseline(&mut self) -> Result<(), String> {
+        let Some(path) = &self.baseline else {
+            return Ok(());
+        };
+        if self.write_baseline {
+            let nonce = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+            let records = format!("clippy-baseline-{}-{}.jsonl", process::id(), nonce.as_nanos());
+            self.clippy_args.baseline_records = Some(env::temp_dir().join(records));
+        } else {
+            // Checked once here rather than by the driver of every crate
+            baseline::Baseline::load(path)?;
+            self.clippy_args.baseline = Some(absolute_path(path));
+        }
+        Ok(())
+    }
+
     fn path() -> PathBuf {
         let mut path = env::current_exe()
             .expect("current executable path invalid")
@@ -111,20 +351,29 @@ impl ClippyCmd {
 
     fn into_std_cmd(self) -> Command {
         let mut cmd = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
-        let clippy_args: String = self
-            .clippy_args
-            .iter()
-            .fold(String::new(), |s, arg| s + arg + "__CLIPPY_HACKERY__");
+        let mut clippy_args = self.clippy_args.clone();
+        clippy_args.profile = env::var("CLIPPY_PROFILE").ok();
+        if self.print_config {
+            // `clippy-driver` tracks the value, a new one makes Cargo re-run it on every crate instead
+            // of replaying the cached output, which doesn't contain the configuration.
+            let nonce = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+            clippy_args.print_config = Some(nonce.as_nanos().to_string());
+        }
 
         // Currently, `CLIPPY_TERMINAL_WIDTH` is used only to format "unknown field" error messages.
         let terminal_width = termize::dimensions().map_or(0, |(w, _)| w);
 
         cmd.env("RUSTC_WORKSPACE_WRAPPER", Self::path())
-            .env("CLIPPY_ARGS", clippy_args)
+            .env("CLIPPY_ARGS", clippy_args.to_env_value())
             .env("CLIPPY_TERMINAL_WIDTH", terminal_width.to_string())
             .arg(self.cargo_subcommand)
             .args(&self.args);
 
+        if self.json_diagnostics() {
+            // The rendered diagnostics are printed as usual once processed.
+            cmd.arg("--message-format=json-diagnostic-rendered-ansi");
+        }
+
         cmd
     }
 }
@@ -133,20 +382,233 @@ fn process<I>(old_args: I) -> Result<(), i32>
 where
     I: Iterator<Item = String>,
 {
-    let cmd = ClippyCmd::new(old_args);
-
-    let mut cmd = cmd.into_std_cmd();
+    let mut cmd = ClippyCmd::new(old_args);
 
-    let exit_status = cmd
-        .spawn()
-        .expect("could not run cargo")
-        .wait()
-        .expect("failed to wait for cargo?");
+    if cmd.json_diagnostics() && cmd.args.iter().any(|arg| arg.starts_with("--message-format")) {
+        eprintln!("error: `--sarif`, `--summary` and `--watch` can't be used with `--message-format`");
+        return Err(rustc_driver::EXIT_FAILURE);
+    }
+    if let Some(applicability) = &cmd.fix_applicability {
+        if cmd.cargo_subcommand != "fix" {
+            eprintln!("error: `--fix-applicability` can only be used with `--fix` or `--fix-only`");
+            return Err(rustc_driver::EXIT_FAILURE);
+        }
+        if !matches!(applicability.as_str(), "machine-applicable" | "maybe-incorrect") {
+            eprintln!(
+                "error: unknown applicability `{applicability}`, expected `machine-applicable` or `maybe-incorrect`"
+            );
+            return Err(rustc_driver::EXIT_FAILURE);
+        }
+    }
+    if cmd.fix_only.as_ref().is_some_and(Vec::is_empty) {
+        eprintln!("error: `--fix-only` needs a comma-separated list of lints");
+        return Err(rustc_driver::EXIT_FAILURE);
+    }
+    if let Some(lint) = cmd.fix_only.iter().flatten().find(|lint| {
+        !clippy_lints::declared_lints::LINTS
+            .iter()
+            .any(|info| info.name_lower() == **lint)
+    }) {
+        eprintln!("error: `--fix-only` was given `{lint}`, which isn't a Clippy lint");
+        return Err(rustc_driver::EXIT_FAILURE);
+    }
+    if cmd.write_baseline && cmd.baseline.is_none() {
+        eprintln!("error: `--write-baseline` needs the path of the baseline, given with `--baseline`");
+        return Err(rustc_driver::EXIT_FAILURE);
+    }
+    if cmd.watch && (cmd.cargo_subcommand == "fix" || cmd.write_baseline) {
+        // Both change the files, which would start the next run
+        eprintln!("error: `--watch` can't be used with `--fix`, `--fix-only` or `--write-baseline`");
+        return Err(rustc_driver::EXIT_FAILURE);
+    }
 
-    if exit_status.success() {
-        Ok(())
+    if cmd.watch {
+        return watch(&cmd);
+    }
+    cmd.load_changes().and_then(|()| cmd.load_baseline()).map_err(|e| {
+        eprintln!("error: {e}");
+        rustc_driver::EXIT_FAILURE
+    })?;
+
+    let write_baseline = cmd.baseline.clone().zip(cmd.clippy_args.baseline_records.clone());
+    let result = if cmd.json_diagnostics() {
+        JsonDiagnostics::new(&cmd).run(cmd.into_std_cmd())
     } else {
-        Err(exit_status.code().unwrap_or(-1))
+        let exit_status = cmd
+            .into_std_cmd()
+            .spawn()
+            .expect("could not run cargo")
+            .wait()
+            .expect("failed to wait for cargo?");
+        if exit_status.success() {
+            Ok(())
+        } else {
+            Err(exit_status.code().unwrap_or(-1))
+        }
+    };
+
+    if let Some((path, records)) = write_baseline {
+        let written = baseline::write(&path, &records);
+        let _ = fs::remove_file(&records);
+        match written {
+            Ok(count) => eprintln!(
+                "note: recorded {} in the baseline `{}`",
+                baseline::plural_diagnostics(count),
+                path.display()
+            ),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return Err(rustc_driver::EXIT_FAILURE);
+            },
+        }
+    }
+    result
+}
+
+/// Runs Clippy every time the files of the workspace change, for `--watch`. The crates which didn't
+/// change aren't linted again, Cargo replays their diagnostics instead.
+fn watch(cmd: &ClippyCmd) -> Result<(), i32> {
+    let ignored = cmd.sarif.iter().chain(&cmd.baseline).cloned();
+    let files = WatchedFiles::new(&cmd.args, ignored).map_err(|e| {
+        eprintln!("error: {e}");
+        rustc_driver::EXIT_FAILURE
+    })?;
+    loop {
+        // Created before running Cargo, so that the changes made meanwhile start the next run
+        let watcher = Watcher::new(&files).map_err(|e| {
+            eprintln!("error: couldn't watch the files of the workspace: {e}");
+            rustc_driver::EXIT_FAILURE
+        })?;
+        if io::stderr().is_terminal() {
+            // Clear the screen and the scrollback, and move the cursor to the top left corner
+            let _ = write!(anstream::stderr().lock(), "\x1b[2J\x1b[3J\x1b[H");
+        }
+        let mut run_cmd = cmd.clone();
+        match run_cmd.load_changes().and_then(|()| run_cmd.load_baseline()) {
+            // Failures are reported by Cargo, and the next change may fix them
+            Ok(()) => drop(JsonDiagnostics::new(&run_cmd).run(run_cmd.into_std_cmd())),
+            Err(e) => eprintln!("error: {e}"),
+        }
+        eprintln!("Waiting for changes, press Ctrl-C to stop");
+        watcher.wait().map_err(|e| {
+            eprintln!("error: couldn't watch the files of the workspace: {e}");
+            rustc_driver::EXIT_FAILURE
+        })?;
+    }
+}
+
+/// Makes the directory of `--conf-dir` absolute, keeping it as is if the current directory is
+/// unavailable.
+fn absolute_path(dir: impl Into<PathBuf>) -> PathBuf {
+    let dir = dir.into();
+    std::path::absolute(&dir).unwrap_or(dir)
+}
+
+/// Parses the comma-separated lints of `--fix-only`, which may be given like in `--explain`.
+fn parse_lint_list(lints: &str) -> Vec<String> {
+    lints
+        .split(',')
+        .map(|lint| {
+            let lint = lint.trim().to_ascii_lowercase();
+            lint.strip_prefix("clippy::").unwrap_or(&lint).replace('-', "_")
+        })
+        .filter(|lint| !lint.is_empty())
+        .collect()
+}
+
+/// Whether the diagnostic is emitted by a lint, unlike the compilation errors which have no code or
+/// an error code like `E0308`.
+fn is_lint(diag: &Diagnostic) -> bool {
+    diag.code.as_ref().is_some_and(|code| {
+        !code
+            .code
+            .strip_prefix('E')
+            .is_some_and(|number| number.bytes().all(|c| c.is_ascii_digit()))
+    })
+}
+
+/// The processing of the diagnostics when Cargo's JSON messages are used.
+struct JsonDiagnostics {
+    sarif: Option<PathBuf>,
+    summary: Option<Summary>,
+    /// Whether to print the summary on a single line, for `--watch` without `--summary`.
+    compact_summary: bool,
+    /// The rendered diagnostics already reported.
+    seen: HashSet<String>,
+}
+
+impl JsonDiagnostics {
+    fn new(cmd: &ClippyCmd) -> Self {
+        Self {
+            sarif: cmd.sarif.clone(),
+            summary: (cmd.summary || cmd.watch).then(Summary::default),
+            compact_summary: cmd.watch && !cmd.summary,
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Whether the diagnostic is reported for the first time. Cargo reports the diagnostics of a
+    /// file for every target containing it, e.g. for the library and its unit tests, which must
+    /// only be logged and counted once.
+    fn first_report(&mut self, diag: &Diagnostic) -> bool {
+        self.seen
+            .insert(diag.rendered.clone().unwrap_or_else(|| diag.message.clone()))
+    }
+
+    /// Runs Cargo with JSON diagnostics and prints them, also writing them to a SARIF log and
+    /// printing their summary once Cargo is done.
+    fn run(mut self, mut cmd: Command) -> Result<(), i32> {
+        let mut child = cmd.stdout(Stdio::piped()).spawn().expect("could not run cargo");
+        let stdout = child.stdout.take().expect("stdout is piped");
+
+        let mut log = SarifLog::default();
+        for message in Message::parse_stream(BufReader::new(stdout)) {
+            match message {
+                Ok(Message::CompilerMessage(message)) => {
+                    let diag = &message.message;
+                    if let Some(rendered) = &diag.rendered {
+                        let _ = write!(anstream::stderr().lock(), "{rendered}");
+                    }
+                    if !self.first_report(diag) {
+                        continue;
+                    }
+                    log.add(diag);
+                    if let Some(summary) = &mut self.summary
+                        && is_lint(diag)
+                    {
+                        summary.add(&message.target.name, diag);
+                    }
+                },
+                Ok(Message::TextLine(line)) => println!("{line}"),
+                Ok(_) => {},
+                Err(e) => {
+                    eprintln!("error: couldn't read the output of Cargo: {e}");
+                    break;
+                },
+            }
+        }
+        let exit_status = child.wait().expect("failed to wait for cargo?");
+
+        if let Some(summary) = &self.summary {
+            let summary = if self.compact_summary {
+                format!("Summary: {}\n", summary.render_compact())
+            } else {
+                summary.render()
+            };
+            let _ = write!(anstream::stderr().lock(), "\n{summary}");
+        }
+        if let Some(path) = &self.sarif
+            && let Err(e) = log.write(path)
+        {
+            eprintln!("error: couldn't write the SARIF log to `{}`: {e}", path.display());
+            return Err(rustc_driver::EXIT_FAILURE);
+        }
+
+        if exit_status.success() {
+            Ok(())
+        } else {
+            Err(exit_status.code().unwrap_or(-1))
+        }
     }
 }
 
@@ -161,9 +623,23 @@ pub fn help_message() -> &'static str {
 <green,bold>Common options:</>
     <cyan,bold>--no-deps</>                Run Clippy only on the given crate, without linting the dependencies
     <cyan,bold>--fix</>                    Automatically apply lint suggestions. This flag implies <cyan>--no-deps</> and <cyan>--all-targets</>
+    <cyan,bold>--fix-only</> <cyan><<LINTS>></>       Like <cyan>--fix</>, but only apply the suggestions of the given comma-separated lints
+    <cyan,bold>--fix-applicability</> <cyan><<A>></>  Also apply the suggestions which may be incorrect with <cyan>maybe-incorrect</>
     <cyan,bold>-h</>, <cyan,bold>--help</>               Print this message
     <cyan,bold>-V</>, <cyan,bold>--version</>            Print version info and exit
-    <cyan,bold>--explain [LINT]</>         Print the documentation for a given lint
+    <cyan,bold>--explain [LINT]</>         Print the documentation for a given lint, with the values of its configuration options
+    <cyan,bold>--list-lints</>             Print every lint with its group and default level, or all its metadata with <cyan>--format json</>
+    <cyan,bold>--print-config</>           Print the configuration of each linted crate, with the origin of every value
+    <cyan,bold>--sarif</> <cyan><<FILE>></>           Also write the diagnostics of all the crates to <cyan><<FILE>></> as a SARIF 2.1.0 log
+    <cyan,bold>--baseline</> <cyan><<FILE>></>        Don't report the Clippy diagnostics recorded in the baseline <cyan><<FILE>></>
+    <cyan,bold>--write-baseline</>         Record all the Clippy diagnostics in the baseline given with <cyan>--baseline</>
+    <cyan,bold>--changed-since</> <cyan><<REV>></>    Only report the lints in the lines changed since the Git revision <cyan><<REV>></>
+    <cyan,bold>--changed-lines</> <cyan><<DIFF>></>   Only report the lints in the lines added by the unified diff <cyan><<DIFF>></>
+    <cyan,bold>--lint-profile</> <cyan><<NAME>></>   Set the lint levels of the profile <cyan><<NAME>></> of the configuration file
+    <cyan,bold>--conf-dir</> <cyan><<DIR>></>         Look for the configuration file in <cyan><<DIR>></> instead of the directory of each package
+    <cyan,bold>--summary</>                Print the number of lints emitted by lint and by crate, and how many <cyan>--fix</> can fix
+    <cyan,bold>--watch</>                  Run again every time the files of the workspace change
+    <cyan,bold>--config-schema</>          Print the JSON Schema of the configuration file
 
 See all options with <cyan,bold>cargo check --help</>.
 
@@ -190,6 +666,15 @@ You can use tool lints to allow or deny lints from your code, e.g.:
 #[cfg(test)]
 mod tests {
     use super::ClippyCmd;
+    use crate::changed_lines::ChangeSource;
+    use clippy_config::clippy_args::ClippyArgs;
+    use std::process::Command;
+
+    fn clippy_args_env(cmd: &Command) -> ClippyArgs {
+        let (_, value) = cmd.get_envs().find(|(key, _)| *key == "CLIPPY_ARGS").unwrap();
+        let value = value.unwrap().to_str().unwrap();
+        ClippyArgs::from_env_value(value, &rustc_tools_util::get_version_info!().to_string()).unwrap()
+    }
 
     #[test]
     fn fix() {
@@ -199,20 +684,158 @@ mod tests {
         assert!(!cmd.args.iter().any(|arg| arg.ends_with("unstable-options")));
     }
 
+    #[test]
+    fn fix_only() {
+        let args =
+            "cargo clippy --fix-only clippy::needless_return,Redundant-Clone --fix-applicability=maybe-incorrect"
+                .split_whitespace()
+                .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!("fix", cmd.cargo_subcommand);
+        assert_eq!(
+            cmd.fix_only.as_deref(),
+            Some(&["needless_return".to_string(), "redundant_clone".to_string()][..])
+        );
+        assert_eq!(cmd.args, ["cargo", "clippy"]);
+        assert!(cmd.clippy_args.no_deps);
+        assert_eq!(cmd.clippy_args.fix_only, cmd.fix_only);
+        assert!(cmd.clippy_args.fix_maybe_incorrect);
+    }
+
+    #[test]
+    fn fix_only_without_lints() {
+        for args in [
+            "cargo clippy --fix-only",
+            "cargo clippy --fix-only=",
+            "cargo clippy --fix-only ,",
+        ] {
+            let cmd = ClippyCmd::new(args.split_whitespace().map(ToString::to_string));
+            assert_eq!("fix", cmd.cargo_subcommand);
+            assert_eq!(cmd.fix_only, Some(Vec::new()), "{args}");
+        }
+    }
+
     #[test]
     fn fix_implies_no_deps() {
         let args = "cargo clippy --fix".split_whitespace().map(ToString::to_string);
         let cmd = ClippyCmd::new(args);
-        assert!(cmd.clippy_args.iter().any(|arg| arg == "--no-deps"));
+        assert!(cmd.clippy_args.no_deps);
     }
 
     #[test]
-    fn no_deps_not_duplicated_with_fix() {
+    fn no_deps_after_double_dash() {
         let args = "cargo clippy --fix -- --no-deps"
             .split_whitespace()
             .map(ToString::to_string);
         let cmd = ClippyCmd::new(args);
-        assert_eq!(cmd.clippy_args.iter().filter(|arg| *arg == "--no-deps").count(), 1);
+        assert!(cmd.clippy_args.no_deps);
+        assert!(cmd.clippy_args.rustc_args.is_empty());
+    }
+
+    #[test]
+    fn print_config() {
+        let args = "cargo clippy --print-config -- -W clippy::pedantic"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert!(cmd.print_config);
+        assert!(!cmd.args.iter().any(|arg| arg == "--print-config"));
+        let clippy_args = clippy_args_env(&cmd.into_std_cmd());
+        assert!(clippy_args.print_config.is_some());
+    }
+
+    #[test]
+    fn sarif() {
+        let args = "cargo clippy --sarif clippy.sarif -p foo"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(cmd.sarif.as_deref(), Some(std::path::Path::new("clippy.sarif")));
+        assert_eq!(cmd.args, ["cargo", "clippy", "-p", "foo"]);
+        assert!(
+            cmd.into_std_cmd()
+                .get_args()
+                .any(|arg| arg == "--message-format=json-diagnostic-rendered-ansi")
+        );
+    }
+
+    #[test]
+    fn baseline() {
+        let args = "cargo clippy --baseline=clippy-baseline.json --write-baseline"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(
+            cmd.baseline.as_deref(),
+            Some(std::path::Path::new("clippy-baseline.json"))
+        );
+        assert!(cmd.write_baseline);
+        assert_eq!(cmd.args, ["cargo", "clippy"]);
+        assert!(cmd.json_diagnostics());
+    }
+
+    #[test]
+    fn changed_since() {
+        let args = "cargo clippy --changed-since origin/main -- -D warnings"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(cmd.changes, Some(ChangeSource::GitRevision("origin/main".into())));
+        assert_eq!(cmd.args, ["cargo", "clippy"]);
+        assert_eq!(cmd.clippy_args.compiler_args(), ["--deny=warnings"]);
+
+        let args = "cargo clippy --changed-lines=pr.diff"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(cmd.changes, Some(ChangeSource::DiffFile("pr.diff".into())));
+        // The driver drops the other lints
+        assert!(!cmd.json_diagnostics());
+    }
+
+    #[test]
+    fn summary() {
+        let args = "cargo clippy --summary --workspace"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert!(cmd.summary);
+        assert_eq!(cmd.args, ["cargo", "clippy", "--workspace"]);
+        assert!(cmd.json_diagnostics());
+    }
+
+    #[test]
+    fn watch() {
+        let args = "cargo clippy --watch --all-targets"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert!(cmd.watch);
+        assert_eq!(cmd.args, ["cargo", "clippy", "--all-targets"]);
+        assert!(cmd.json_diagnostics());
+    }
+
+    #[test]
+    fn lint_profile() {
+        let args = "cargo clippy --lint-profile library --release -- -Aclippy::unwrap_used"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(cmd.args, ["cargo", "clippy", "--release"]);
+        assert_eq!(cmd.clippy_args.lint_profile.as_deref(), Some("library"));
+        assert_eq!(cmd.clippy_args.compiler_args(), ["--allow=clippy::unwrap_used"]);
+    }
+
+    #[test]
+    fn conf_dir() {
+        let args = "cargo clippy --conf-dir=config"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(cmd.args, ["cargo", "clippy"]);
+        let clippy_args = clippy_args_env(&cmd.into_std_cmd());
+        let conf_dir = clippy_args.conf_dir.unwrap();
+        assert!(conf_dir.is_absolute() && conf_dir.ends_with("config"));
     }
 
     #[test]
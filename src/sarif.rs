@@ -0,0 +1,189 @@
+//! Conversion of the diagnostics of `cargo clippy --sarif` to a [SARIF 2.1.0] log.
+//!
+//! [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use cargo_metadata::diagnostic::{Applicability, Diagnostic, DiagnosticLevel, DiagnosticSpan};
+use clippy_config::sanitize_explanation;
+use clippy_lints::declared_lints::LINTS;
+use clippy_utils::diagnostics::lint_docs_url;
+use serde_json::{Value, json};
+use std::fmt::Write as _;
+use std::path::Path;
+use std::{fs, io};
+
+#[derive(Default)]
+pub struct SarifLog {
+    /// The ids of the lints in `rules`, in the same order.
+    rule_ids: Vec<String>,
+    rules: Vec<Value>,
+    results: Vec<Value>,
+}
+
+impl SarifLog {
+    /// Adds a diagnostic emitted by rustc as a result. Diagnostics without a location, like the
+    /// number of warnings emitted, are ignored.
+    pub fn add(&mut self, diag: &Diagnostic) {
+        let Some(span) = diag.spans.iter().find(|span| span.is_primary) else {
+            return;
+        };
+
+        let mut result = json!({
+            "level": level(diag.level),
+            "message": { "text": message_text(diag) },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": artifact_location(&span.file_name),
+                    "region": region(span),
+                },
+            }],
+        });
+        if let Some(code) = &diag.code {
+            result["ruleId"] = code.code.clone().into();
+            result["ruleIndex"] = self.rule_index(&code.code).into();
+        }
+        let fixes = fixes(diag);
+        if !fixes.is_empty() {
+            result["fixes"] = fixes.into();
+        }
+        self.results.push(result);
+    }
+
+    /// Returns the index of the rule describing the lint `id`, adding it if needed.
+    fn rule_index(&mut self, id: &str) -> usize {
+        if let Some(index) = self.rule_ids.iter().position(|rule_id| rule_id == id) {
+            return index;
+        }
+        self.rule_ids.push(id.to_owned());
+        self.rules.push(rule(id));
+        self.rules.len() - 1
+    }
+
+    pub fn write(self, path: &Path) -> io::Result<()> {
+        let log = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "clippy",
+                        "informationUri": "https://github.com/rust-lang/rust-clippy",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": self.rules,
+                    },
+                },
+                "results": self.results,
+            }],
+        });
+        fs::write(path, format!("{log:#}\n"))
+    }
+}
+
+/// Describes the lint `id`. Only the id is known for the rustc lints and error codes.
+fn rule(id: &str) -> Value {
+    let Some((name, info)) = id
+        .strip_prefix("clippy::")
+        .and_then(|name| Some((name, LINTS.iter().find(|info| info.name_lower() == name)?)))
+    else {
+        return json!({ "id": id });
+    };
+
+    let docs = sanitize_explanation(info.explanation);
+    json!({
+        "id": id,
+        "name": name,
+        "shortDescription": { "text": info.lint.desc },
+        "help": { "text": docs, "markdown": docs },
+        "helpUri": lint_docs_url(name),
+        "defaultConfiguration": {
+            "level": match info.lint.default_level.as_str() {
+                "deny" | "forbid" => "error",
+                "warn" => "warning",
+                _ => "none",
+            },
+        },
+        "properties": { "tags": [info.category.name()] },
+    })
+}
+
+fn level(level: DiagnosticLevel) -> &'static str {
+    match level {
+        DiagnosticLevel::Ice | DiagnosticLevel::Error => "error",
+        DiagnosticLevel::Warning => "warning",
+        _ => "note",
+    }
+}
+
+/// The message of the diagnostic followed by its notes and help messages.
+fn message_text(diag: &Diagnostic) -> String {
+    let mut text = diag.message.clone();
+    for child in &diag.children {
+        let level = match child.level {
+            DiagnosticLevel::Help => "help",
+            _ => "note",
+        };
+        write!(text, "\n{level}: {}", child.message).unwrap();
+    }
+    text
+}
+
+/// Rustc gives the paths of the workspace files relative to the workspace root, which is the root
+/// of the source tree for the SARIF consumers, and the paths of the other files as absolute paths.
+fn artifact_location(file_name: &str) -> Value {
+    let path = file_name.replace('\\', "/");
+    if Path::new(file_name).is_absolute() {
+        let separator = if path.starts_with('/') { "" } else { "/" };
+        json!({ "uri": format!("file://{separator}{path}") })
+    } else {
+        json!({ "uri": path, "uriBaseId": "%SRCROOT%" })
+    }
+}
+
+fn region(span: &DiagnosticSpan) -> Value {
+    json!({
+        "startLine": span.line_start,
+        "startColumn": span.column_start,
+        "endLine": span.line_end,
+        "endColumn": span.column_end,
+    })
+}
+
+/// The machine-applicable suggestions of the diagnostic, one fix per suggestion.
+fn fixes(diag: &Diagnostic) -> Vec<Value> {
+    diag.children
+        .iter()
+        .filter_map(|child| {
+            let mut changes: Vec<(&str, Vec<Value>)> = Vec::new();
+            for span in &child.spans {
+                if span.suggestion_applicability != Some(Applicability::MachineApplicable) {
+                    continue;
+                }
+                let Some(replacement) = &span.suggested_replacement else {
+                    continue;
+                };
+                let replacement = json!({
+                    "deletedRegion": region(span),
+                    "insertedContent": { "text": replacement },
+                });
+                match changes.iter_mut().find(|(file_name, _)| *file_name == span.file_name) {
+                    Some((_, replacements)) => replacements.push(replacement),
+                    None => changes.push((&span.file_name, vec![replacement])),
+                }
+            }
+            (!changes.is_empty()).then(|| {
+                let changes: Vec<Value> = changes
+                    .into_iter()
+                    .map(|(file_name, replacements)| {
+                        json!({
+                            "artifactLocation": artifact_location(file_name),
+                            "replacements": replacements,
+                        })
+                    })
+                    .collect();
+                json!({
+                    "description": { "text": child.message },
+                    "artifactChanges": changes,
+                })
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,124 @@
+//! The lint counts printed by `cargo clippy --summary` once Cargo is done.
+
+use cargo_metadata::diagnostic::{Applicability, Diagnostic, DiagnosticLevel};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+#[derive(Default)]
+struct Counts {
+    warnings: usize,
+    errors: usize,
+    /// The diagnostics with a machine-applicable suggestion, which `--fix` applies.
+    fixable: usize,
+}
+
+impl Counts {
+    fn add(&mut self, diag: &Diagnostic) {
+        if matches!(diag.level, DiagnosticLevel::Error | DiagnosticLevel::Ice) {
+            self.errors += 1;
+        } else {
+            self.warnings += 1;
+        }
+        if is_fixable(diag) {
+            self.fixable += 1;
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.warnings + self.errors
+    }
+}
+
+fn is_fixable(diag: &Diagnostic) -> bool {
+    diag.children.iter().any(|child| {
+        child
+            .spans
+            .iter()
+            .any(|span| span.suggestion_applicability == Some(Applicability::MachineApplicable))
+    })
+}
+
+#[derive(Default)]
+pub struct Summary {
+    lints: BTreeMap<String, Counts>,
+    crates: BTreeMap<String, Counts>,
+}
+
+impl Summary {
+    /// Counts a lint diagnostic of the crate `crate_name`.
+    pub fn add(&mut self, crate_name: &str, diag: &Diagnostic) {
+        let Some(code) = &diag.code else {
+            return;
+        };
+        self.lints.entry(code.code.clone()).or_default().add(diag);
+        self.crates.entry(crate_name.to_owned()).or_default().add(diag);
+    }
+
+    /// Returns the tables of the counts by lint and by crate, sorted from the most frequent, and
+    /// the totals.
+    pub fn render(&self) -> String {
+        let total = self.total();
+        if total.total() == 0 {
+            return "Summary: no lint was emitted\n".into();
+        }
+
+        let mut text = String::from("Summary:\n\n");
+        table(&mut text, "lint", &self.lints);
+        text.push('\n');
+        table(&mut text, "crate", &self.crates);
+        writeln!(
+            text,
+            "\n{} and {}, {} can be fixed with `cargo clippy --fix`",
+            plural(total.warnings, "warning"),
+            plural(total.errors, "error"),
+            total.fixable
+        )
+        .unwrap();
+        text
+    }
+
+    /// Returns the totals on a single line, for `--watch`.
+    pub fn render_compact(&self) -> String {
+        let total = self.total();
+        if total.total() == 0 {
+            return "no lint was emitted".into();
+        }
+        format!(
+            "{} and {} from {} in {}, {} can be fixed with `cargo clippy --fix`",
+            plural(total.warnings, "warning"),
+            plural(total.errors, "error"),
+            plural(self.lints.len(), "lint"),
+            plural(self.crates.len(), "crate"),
+            total.fixable
+        )
+    }
+
+    fn total(&self) -> Counts {
+        self.crates.values().fold(Counts::default(), |total, counts| Counts {
+            warnings: total.warnings + counts.warnings,
+            errors: total.errors + counts.errors,
+            fixable: total.fixable + counts.fixable,
+        })
+    }
+}
+
+fn table(text: &mut String, name: &str, rows: &BTreeMap<String, Counts>) {
+    let mut rows: Vec<_> = rows.iter().collect();
+    // The map is sorted by name, which the stable sort keeps for the same counts
+    rows.sort_by_key(|(_, counts)| std::cmp::Reverse(counts.total()));
+
+    writeln!(text, "| {name:<50} | warnings | errors | fixable |").unwrap();
+    writeln!(text, "| {:-<50} | -------- | ------ | ------- |", "").unwrap();
+    for (name, counts) in rows {
+        writeln!(
+            text,
+            "| {name:<50} | {:>8} | {:>6} | {:>7} |",
+            counts.warnings, counts.errors, counts.fixable
+        )
+        .unwrap();
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    format!("{count} {noun}{}", if count == 1 { "" } else { "s" })
+}
@@ -0,0 +1,162 @@
+//! The file watching of `cargo clippy --watch`, which runs Clippy again once the files of the
+//! workspace change.
+//!
+//! The directories are watched with the `notify` crate, which uses the file system events of the
+//! platform.
+
+use crate::changed_lines;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+use std::{fs, io, thread};
+
+/// How long to wait after a change for the following ones, as editors and tools often write
+/// several files in a row.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// The directories of the workspace and of its members, and the paths whose changes are ignored.
+pub struct WatchedFiles {
+    roots: Vec<PathBuf>,
+    target_dir: PathBuf,
+    ignored: HashSet<PathBuf>,
+}
+
+impl WatchedFiles {
+    /// Finds the directories of the workspace and of its members, without the target directory.
+    /// The changes of the files in `ignored`, which Clippy writes, don't trigger a new run.
+    pub fn new(cargo_args: &[String], ignored: impl IntoIterator<Item = PathBuf>) -> Result<Self, String> {
+        let mut cmd = cargo_metadata::MetadataCommand::new();
+        cmd.no_deps();
+        if let Some(path) = changed_lines::manifest_path(cargo_args) {
+            cmd.manifest_path(path);
+        }
+        let metadata = cmd
+            .exec()
+            .map_err(|e| format!("couldn't find the files of the workspace: {e}"))?;
+
+        let mut roots = vec![metadata.workspace_root.clone().into_std_path_buf()];
+        for package in metadata.workspace_packages() {
+            if let Some(dir) = package.manifest_path.parent()
+                && !dir.starts_with(&metadata.workspace_root)
+            {
+                roots.push(dir.to_path_buf().into_std_path_buf());
+            }
+        }
+        let target_dir = metadata.target_directory.into_std_path_buf();
+        let ignored = ignored.into_iter().map(absolute_path).collect();
+        Ok(Self {
+            roots,
+            target_dir,
+            ignored,
+        })
+    }
+
+    /// Lists the directories containing the files of the workspace, found again for each run so
+    /// that the directories created since the previous one are watched.
+    fn dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        for root in &self.roots {
+            walk_dirs(root.clone(), &self.target_dir, &mut dirs);
+        }
+        dirs
+    }
+
+    /// Whether a change of the file at `path` should trigger a new run. The hidden files, like
+    /// `.git` or the swap files of editors, and the backup files are ignored, but not Clippy's
+    /// configuration.
+    fn is_relevant(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name().map(|name| name.to_string_lossy()) else {
+            return false;
+        };
+        if path.starts_with(&self.target_dir) || self.ignored.contains(path) {
+            return false;
+        }
+        matches!(&*name, "clippy.toml" | ".clippy.toml") || !name.starts_with('.') && !name.ends_with('~')
+    }
+}
+
+/// Makes `path` absolute like the directories of [`WatchedFiles`]. Only its parent directory is
+/// canonicalized, as the file may not exist yet, like the output of `--sarif`.
+fn absolute_path(path: PathBuf) -> PathBuf {
+    let path = std::path::absolute(&path).unwrap_or(path);
+    match (path.parent().and_then(|dir| dir.canonicalize().ok()), path.file_name()) {
+        (Some(dir), Some(name)) => dir.join(name),
+        _ => path,
+    }
+}
+
+/// Adds `dir` and its subdirectories to `dirs`, except for the hidden ones and `target_dir`.
+fn walk_dirs(dir: PathBuf, target_dir: &Path, dirs: &mut Vec<PathBuf>) {
+    if dir == target_dir || dirs.contains(&dir) {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return;
+    };
+    let subdirs: Vec<_> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry.file_type().is_ok_and(|file_type| file_type.is_dir())
+                && !entry.file_name().to_string_lossy().starts_with('.')
+        })
+        .map(|entry| entry.path())
+        .collect();
+    dirs.push(dir);
+    for subdir in subdirs {
+        walk_dirs(subdir, target_dir, dirs);
+    }
+}
+
+/// Watches the files from its creation, so that the changes made while Clippy runs aren't missed.
+pub struct Watcher<'a> {
+    files: &'a WatchedFiles,
+    /// Stops watching the files once dropped.
+    _notifier: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+}
+
+impl<'a> Watcher<'a> {
+    pub fn new(files: &'a WatchedFiles) -> io::Result<Self> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender).map_err(io::Error::other)?;
+        // Each directory is watched on its own, as the target directory and the hidden ones may be
+        // large
+        for dir in files.dirs() {
+            match watcher.watch(&dir, RecursiveMode::NonRecursive) {
+                // The directory may have been removed since it was found
+                Ok(())
+                | Err(notify::Error {
+                    kind: notify::ErrorKind::PathNotFound,
+                    ..
+                }) => {},
+                Err(e) => return Err(io::Error::other(e)),
+            }
+        }
+        Ok(Self {
+            files,
+            _notifier: watcher,
+            events,
+        })
+    }
+
+    /// Blocks until a file changes, then waits for the changes that follow it.
+    pub fn wait(self) -> io::Result<()> {
+        loop {
+            let event = self
+                .events
+                .recv()
+                .map_err(io::Error::other)?
+                .map_err(io::Error::other)?;
+            // Reading the files, like Cargo does, changes nothing
+            if !matches!(event.kind, EventKind::Access(_))
+                && event.paths.iter().any(|path| self.files.is_relevant(path))
+            {
+                break;
+            }
+        }
+        thread::sleep(DEBOUNCE);
+        Ok(())
+    }
+}
@@ -552,6 +552,18 @@ impl DiagnosticCollector {
 
             metadata.sort_unstable_by(|a, b| a.id.cmp(&b.id));
 
+            // Also listed by `cargo clippy --list-lints`
+            let applicabilities: BTreeMap<&str, &str> = metadata
+                .iter()
+                .filter(|lint| lint.group != "deprecated")
+                .map(|lint| (lint.id.as_str(), lint.applicability_str()))
+                .collect();
+            fs::write(
+                "clippy_lints/src/applicabilities.json",
+                serde_json::to_string_pretty(&applicabilities).unwrap() + "\n",
+            )
+            .unwrap();
+
             fs::write(
                 "util/gh-pages/index.html",
                 Renderer {
@@ -1,6 +1,6 @@
 #![feature(rustc_private)]
 
-use clippy_config::{ClippyConfiguration, get_configuration_metadata};
+use clippy_config::{ClippyConfiguration, configuration_json_schema, get_configuration_metadata};
 use itertools::Itertools;
 use regex::Regex;
 use std::borrow::Cow;
@@ -76,3 +76,26 @@ fn changelog() {
         }
     }
 }
+
+#[test]
+fn json_schema() {
+    let schema = configuration_json_schema();
+    let properties = schema["properties"].as_object().unwrap();
+
+    for conf in get_configuration_metadata() {
+        let property = &properties[&conf.name];
+        assert!(
+            ["type", "enum", "anyOf"].iter().any(|key| property.get(key).is_some()),
+            "`{}` has no type in the JSON schema",
+            conf.name
+        );
+        assert!(
+            property["description"].is_string(),
+            "`{}` has no description",
+            conf.name
+        );
+    }
+    for key in ["inherit", "extends", "overrides", "third-party"] {
+        assert!(properties.contains_key(key), "`{key}` is missing from the JSON schema");
+    }
+}
@@ -0,0 +1,25 @@
+use std::process::Command;
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+#[test]
+fn explain_with_configuration() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .env("CLIPPY_CONF_DIR", "tests/ui-toml/manual_let_else")
+        .args(["clippy", "--explain", "clippy::manual_let_else"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    println!("stdout: {stdout}");
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+
+    assert!(stdout.contains("### What it does"));
+    assert!(stdout.contains("- Group: `pedantic`\n- Default level: `allow`\n- Added in: 1.67.0\n"));
+    assert!(stdout.contains("- Respects the `msrv` configuration option, which is currently not set\n"));
+    assert!(stdout.contains("| `matches-for-let-else` | `\"AllTypes\"` | `\"WellKnownTypes\"` |\n"));
+}
@@ -0,0 +1,65 @@
+use serde_json::Value;
+use std::process::Command;
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+fn list_lints(format: &str) -> String {
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .args(["clippy", "--list-lints", "--format", format])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn json() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let lints: Vec<Value> = serde_json::from_str(&list_lints("json")).unwrap();
+    let lint = |id: &str| {
+        lints
+            .iter()
+            .find(|lint| lint["id"] == id)
+            .unwrap_or_else(|| panic!("`{id}` isn't listed"))
+    };
+
+    let needless_return = lint("needless_return");
+    assert_eq!(needless_return["group"], "style");
+    assert_eq!(needless_return["level"], "warn");
+    assert_eq!(needless_return["msrv_gated"], false);
+    assert_eq!(needless_return["applicability"], "MachineApplicable");
+    assert!(needless_return["docs"].as_str().unwrap().contains("### What it does"));
+
+    let manual_let_else = lint("manual_let_else");
+    assert_eq!(manual_let_else["group"], "pedantic");
+    assert_eq!(manual_let_else["msrv_gated"], true);
+    assert!(
+        manual_let_else["config"]
+            .as_array()
+            .unwrap()
+            .contains(&Value::from("matches-for-let-else"))
+    );
+
+    assert_eq!(lint("assign_ops")["group"], "deprecated");
+    assert_eq!(lint("assign_ops")["applicability"], "Unspecified");
+}
+
+#[test]
+fn text() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let lints = list_lints("text");
+    assert!(
+        lints
+            .lines()
+            .any(|line| line.split_whitespace().eq(["clippy::needless_return", "style", "warn"]))
+    );
+}
@@ -0,0 +1,6 @@
+#![deny(clippy::disallowed_names)]
+
+fn main() {
+    let toto = 42;
+    let _ = toto;
+}
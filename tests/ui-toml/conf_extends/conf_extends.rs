@@ -0,0 +1,13 @@
+#![warn(clippy::disallowed_methods)]
+
+fn from_base() {}
+fn from_member() {}
+
+fn main() {
+    // disallowed by the extended configuration
+    from_base();
+    //~^ disallowed_methods
+    // disallowed by this configuration
+    from_member();
+    //~^ disallowed_methods
+}
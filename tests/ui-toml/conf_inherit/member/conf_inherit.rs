@@ -0,0 +1,15 @@
+#![warn(clippy::disallowed_names)]
+
+fn main() {
+    // `toto` is disallowed by the inherited configuration
+    let toto = 1;
+    //~^ disallowed_names
+    // `tata` is disallowed by this configuration
+    let tata = 2;
+    //~^ disallowed_names
+    // `foo` is part of the default configuration
+    let foo = 3;
+    //~^ disallowed_names
+    // `titi` is okay
+    let titi = 4;
+}
@@ -0,0 +1,16 @@
+// `too-many-lines-threshold` is overridden for this file
+pub fn not_too_many_lines() {
+    println!("This is fine.");
+    println!("This is fine.");
+}
+
+// `clippy::unwrap_used` is allowed in this file
+pub fn allowed_unwrap(x: Option<u32>) -> u32 {
+    x.unwrap()
+}
+
+// The attributes of this file take precedence over `allow`
+#[expect(clippy::unwrap_used)]
+pub fn expected_unwrap(x: Option<u32>) -> u32 {
+    x.unwrap()
+}
@@ -0,0 +1,18 @@
+//@error-in-other-file: unknown lint
+#![warn(clippy::too_many_lines, clippy::unwrap_used)]
+
+#[path = "auxiliary/generated_code.rs"]
+mod generated_code;
+
+fn too_many_lines() {
+    //~^ too_many_lines
+    println!("This is bad.");
+    println!("This is bad.");
+}
+
+fn unwrap(x: Option<u32>) -> u32 {
+    x.unwrap()
+    //~^ unwrap_used
+}
+
+fn main() {}
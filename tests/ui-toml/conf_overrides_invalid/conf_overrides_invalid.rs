@@ -0,0 +1,7 @@
+//@error-in-other-file: `paths` can only be used in `[[overrides]]`
+//@error-in-other-file: `inherit` can't be used in `[[overrides]]`
+//@error-in-other-file: the lint group `clippy::pedantic` can't be allowed in `[[overrides]]`
+//@error-in-other-file: `unused_mut` isn't one of Clippy's lints
+//@error-in-other-file: `disallowed-names` can't be set in `[[overrides]]`
+
+fn main() {}
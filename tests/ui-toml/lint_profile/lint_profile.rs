@@ -0,0 +1,20 @@
+//@rustc-env:CLIPPY_ARGS={"version":1,"lint_profile":"strict"}
+// The `strict` profile of `clippy.toml` warns about the pedantic lints except
+// `must_use_candidate`, and denies `unwrap_used`
+
+pub fn answer() -> u8 {
+    42
+}
+
+fn first(values: &[u8]) -> u8 {
+    values.first().copied().unwrap()
+    //~^ unwrap_used
+}
+
+fn main() {
+    let value = first(&[answer()]);
+    fn item() {}
+    //~^ items_after_statements
+    item();
+    let _ = value;
+}
@@ -0,0 +1,27 @@
+#![warn(clippy::disallowed_macros, clippy::disallowed_methods)]
+
+mod cli {
+    pub fn run() {
+        // `crate::cli::*` allows `cli` too
+        println!("running");
+        std::process::exit(0);
+    }
+
+    pub mod commands {
+        pub fn help() {
+            println!("help");
+            std::process::exit(0);
+        }
+    }
+}
+
+fn main() {
+    cli::run();
+    cli::commands::help();
+
+    std::mem::forget(String::new());
+    std::process::exit(1);
+    //~^ disallowed_methods
+    println!("done");
+    //~^ disallowed_macros
+}
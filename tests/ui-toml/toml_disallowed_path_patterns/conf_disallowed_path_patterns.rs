@@ -0,0 +1,28 @@
+//@error-in-other-file: `std::*::exit` has wildcards before its last segment, which isn't supported
+//@error-in-other-file: `std::env::does_not_exist_*` does not match any reachable function
+#![warn(clippy::disallowed_methods, clippy::disallowed_types)]
+
+use std::io::Write;
+
+mod helpers {
+    pub fn f() {}
+}
+
+fn main() {
+    let _ = std::env::set_current_dir("/");
+    //~^ disallowed_methods
+
+    let mut out = Vec::new();
+    let _ = out.write_all(b"hello");
+    //~^ disallowed_methods
+
+    let _: Vec<u8> = [1].into_iter().collect();
+    let _: std::collections::HashSet<u8> = [1].into_iter().collect();
+    //~^ disallowed_methods
+
+    helpers::f();
+    //~^ disallowed_methods
+}
+
+fn channel(_: std::sync::mpsc::Sender<u8>) {}
+//~^ disallowed_types
@@ -0,0 +1,49 @@
+#![feature(negative_impls)]
+#![warn(clippy::disallowed_trait_impls)]
+
+use std::ops::Deref;
+
+mod handle {
+    #[derive(Clone)]
+    //~^ disallowed_trait_impls
+    pub struct FileHandle(pub i32);
+
+    pub struct Socket(pub i32);
+
+    impl Clone for Socket {
+        //~^ disallowed_trait_impls
+        fn clone(&self) -> Self {
+            Socket(self.0)
+        }
+    }
+
+    // Negative implementations don't implement the trait
+    pub struct Token;
+    impl !Clone for Token {}
+}
+
+// `Clone` is only disallowed for the types in `handle`
+#[derive(Clone)]
+struct Config;
+
+struct Wrapper(String);
+
+impl Deref for Wrapper {
+    //~^ disallowed_trait_impls
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        &self.0
+    }
+}
+
+mod debugging {
+    #[derive(Debug)]
+    pub struct Dump;
+}
+
+#[derive(Debug)]
+//~^ disallowed_trait_impls
+struct Secret;
+
+fn main() {}
@@ -0,0 +1,2 @@
+//@error-in-other-file:
+fn main() {}
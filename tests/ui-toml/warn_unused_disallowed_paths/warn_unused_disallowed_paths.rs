@@ -0,0 +1,25 @@
+//@error-in-other-file: the disallowed macro `std::eprintln` is never used in this crate
+//@error-in-other-file: the disallowed function `std::process::exit` is never used in this crate
+//@error-in-other-file: the disallowed type `std::collections::BTreeMap` is never used in this crate
+//@error-in-other-file: the disallowed field `std::ops::Range::end` is never used in this crate
+#![warn(
+    clippy::disallowed_fields,
+    clippy::disallowed_macros,
+    clippy::disallowed_methods,
+    clippy::disallowed_types
+)]
+
+fn main() {
+    println!("hello");
+    //~^ disallowed_macros
+
+    let _ = vec![1].leak();
+    //~^ disallowed_methods
+
+    let range = 0..1;
+    let _ = range.start;
+    //~^ disallowed_fields
+}
+
+fn f(_: std::collections::HashMap<u8, u8>) {}
+//~^ disallowed_types
@@ -143,3 +143,259 @@ fn test_no_deps_ignores_path_deps_in_workspaces() {
     // Make sure Cargo is aware of the new `--cfg` flag.
     lint_path_dep();
 }
+
+#[test]
+fn test_sarif_output() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("workspace_test");
+    let cwd = root.join("tests/workspace_test");
+    let sarif_path = target_dir.join("clippy.sarif");
+
+    // `path_dep` triggers `clippy::empty_loop`, which is denied.
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(&cwd)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .arg("clippy")
+        .args(["-p", "path_dep"])
+        .arg("--sarif")
+        .arg(&sarif_path)
+        .arg("--")
+        .arg("-Cdebuginfo=0") // disable debuginfo to generate less data in the target dir
+        .args(["--cfg", r#"feature="primary_package_test""#])
+        .output()
+        .unwrap();
+    println!("status: {}", output.status);
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    assert!(!output.status.success());
+    // The diagnostics are still printed.
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("error: empty `loop {}` wastes CPU cycles")
+    );
+
+    let log: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&sarif_path).unwrap()).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "clippy::empty_loop");
+    assert_eq!(result["level"], "error");
+    assert_eq!(
+        result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "path_dep/src/lib.rs"
+    );
+    assert_eq!(result["ruleIndex"], 0);
+    let rule = &run["tool"]["driver"]["rules"][0];
+    assert_eq!(rule["id"], "clippy::empty_loop");
+    assert_eq!(rule["properties"]["tags"][0], "suspicious");
+    assert!(rule["helpUri"].as_str().unwrap().ends_with("#empty_loop"));
+}
+
+#[test]
+fn test_baseline() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("workspace_test");
+    let cwd = root.join("tests/workspace_test");
+    let baseline_path = target_dir.join("clippy-baseline.json");
+
+    // `path_dep` triggers `clippy::empty_loop`, which is denied.
+    let run_clippy = |write_baseline: bool| {
+        let mut cmd = Command::new(&*CARGO_CLIPPY_PATH);
+        cmd.current_dir(&cwd)
+            .env("CARGO_INCREMENTAL", "0")
+            .env("CARGO_TARGET_DIR", &target_dir)
+            .arg("clippy")
+            .args(["-p", "path_dep"])
+            .arg("--baseline")
+            .arg(&baseline_path);
+        if write_baseline {
+            cmd.arg("--write-baseline");
+        }
+        let output = cmd
+            .arg("--")
+            .arg("-Cdebuginfo=0") // disable debuginfo to generate less data in the target dir
+            .args(["--cfg", r#"feature="primary_package_test""#])
+            .output()
+            .unwrap();
+        println!("status: {}", output.status);
+        println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+        println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+        output
+    };
+
+    let output = run_clippy(true);
+    assert!(output.status.success());
+    let baseline: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&baseline_path).unwrap()).unwrap();
+    let entry = &baseline["entries"][0];
+    assert_eq!(entry["lint"], "clippy::empty_loop");
+    assert_eq!(entry["file"], "path_dep/src/lib.rs");
+    assert_eq!(entry["count"], 1);
+
+    // The error is recorded in the baseline, so it isn't reported and doesn't make Clippy fail.
+    let output = run_clippy(false);
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("empty `loop {}` wastes CPU cycles"));
+    assert!(stderr.contains("suppressed 1 diagnostic recorded in the baseline"));
+    assert!(!stderr.contains("could not compile"));
+}
+
+#[test]
+fn test_changed_lines() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("workspace_test");
+    let cwd = root.join("tests/workspace_test");
+    let diff_path = target_dir.join("changes.diff");
+
+    // `path_dep` triggers `clippy::empty_loop` on line 5, which is denied.
+    let run_clippy = |changed_line: usize| {
+        std::fs::create_dir_all(&target_dir).unwrap();
+        std::fs::write(
+            &diff_path,
+            format!(
+                "--- a/path_dep/src/lib.rs\n+++ b/path_dep/src/lib.rs\n@@ -{changed_line},0 +{changed_line},1 @@\n+changed\n"
+            ),
+        )
+        .unwrap();
+        let output = Command::new(&*CARGO_CLIPPY_PATH)
+            .current_dir(&cwd)
+            .env("CARGO_INCREMENTAL", "0")
+            .env("CARGO_TARGET_DIR", &target_dir)
+            .arg("clippy")
+            .args(["-p", "path_dep"])
+            .arg("--changed-lines")
+            .arg(&diff_path)
+            .arg("--")
+            .arg("-Cdebuginfo=0") // disable debuginfo to generate less data in the target dir
+            .args(["--cfg", r#"feature="primary_package_test""#])
+            .output()
+            .unwrap();
+        println!("status: {}", output.status);
+        println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+        println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+        output
+    };
+
+    // The lint is dropped by the driver, so the crate compiles.
+    let output = run_clippy(1);
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("empty `loop {}` wastes CPU cycles"));
+    assert!(!stderr.contains("could not compile"));
+
+    let output = run_clippy(5);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("error: empty `loop {}` wastes CPU cycles")
+    );
+}
+
+#[test]
+fn test_profile() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("workspace_test");
+    let cwd = root.join("tests/workspace_test");
+    // Cargo lints the crate again when `CLIPPY_PROFILE` changes, so every run uses a new file.
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let profile_path = target_dir.join(format!("clippy-profile-{nonce}.jsonl"));
+
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(&cwd)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .env("CLIPPY_PROFILE", format!("json:{}", profile_path.display()))
+        .arg("clippy")
+        .args(["-p", "path_dep"])
+        .arg("--")
+        .arg("-Cdebuginfo=0") // disable debuginfo to generate less data in the target dir
+        .output()
+        .unwrap();
+    println!("status: {}", output.status);
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+
+    let contents = std::fs::read_to_string(&profile_path).unwrap();
+    std::fs::remove_file(&profile_path).unwrap();
+    let profile: serde_json::Value = serde_json::from_str(contents.lines().next().unwrap()).unwrap();
+    assert_eq!(profile["crate"], "path_dep");
+    assert!(profile["total_ms"].as_f64().unwrap() > 0.0);
+    let passes = profile["passes"].as_array().unwrap();
+    // Sorted from the slowest pass
+    assert!(
+        passes
+            .windows(2)
+            .all(|pair| pair[0]["time_ms"].as_f64() >= pair[1]["time_ms"].as_f64())
+    );
+    assert!(passes.iter().any(|pass| {
+        pass["kind"] == "late"
+            && pass["lints"]
+                .as_array()
+                .unwrap()
+                .contains(&serde_json::Value::from("clippy::empty_loop"))
+    }));
+}
+
+#[test]
+fn test_summary() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("workspace_test");
+    let cwd = root.join("tests/workspace_test");
+
+    // `path_dep` triggers `clippy::empty_loop`, which is denied.
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(&cwd)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .arg("clippy")
+        .args(["-p", "path_dep"])
+        .arg("--summary")
+        .arg("--")
+        .arg("-Cdebuginfo=0") // disable debuginfo to generate less data in the target dir
+        .args(["--cfg", r#"feature="primary_package_test""#])
+        .output()
+        .unwrap();
+    println!("status: {}", output.status);
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("error: empty `loop {}` wastes CPU cycles"));
+    let row = |name: &str| {
+        stderr
+            .lines()
+            .find(|line| line.starts_with(&format!("| {name} ")))
+            .unwrap_or_else(|| panic!("no row for `{name}`"))
+            .split('|')
+            .map(str::trim)
+            .filter(|cell| !cell.is_empty())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(row("clippy::empty_loop"), ["clippy::empty_loop", "0", "1", "0"]);
+    assert_eq!(row("path_dep"), ["path_dep", "0", "1", "0"]);
+    assert!(stderr.contains("0 warnings and 1 error, 0 can be fixed with `cargo clippy --fix`"));
+}